@@ -0,0 +1,594 @@
+//! # eventsourcingdb-derive
+//!
+//! Derive macros for the [eventsourcingdb](https://docs.rs/eventsourcingdb) client SDK.
+//!
+//! **You should not use this crate directly**, but enable the `derive` feature of the `eventsourcingdb` crate instead.
+#![deny(
+    clippy::pedantic,
+    missing_debug_implementations,
+    missing_docs,
+    unused_results,
+    unsafe_code,
+    warnings
+)]
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    Attribute, Data, DataEnum, DeriveInput, Fields, Ident, Index, LitStr, Member, Token, Variant,
+    meta::ParseNestedMeta, parenthesized, parse_macro_input, parse_quote, spanned::Spanned,
+    token::Paren,
+};
+
+/// Derives `eventsourcingdb::event::EventSourcingDbEvent` for a struct or an enum.
+///
+/// See the documentation of the `EventSourcingDbEvent` trait in the `eventsourcingdb` crate for details.
+#[proc_macro_derive(EventSourcingDbEvent, attributes(esdb))]
+pub fn derive_event_sourcing_db_event(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// The values of the `#[esdb(...)]` attributes on a type or a variant.
+#[derive(Default)]
+struct EsdbAttributes {
+    ty: Option<LitStr>,
+    subject: Option<LitStr>,
+    source: Option<LitStr>,
+}
+
+impl EsdbAttributes {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut result = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("esdb")) {
+            attr.parse_nested_meta(|meta| {
+                let slot = if meta.path.is_ident("type") {
+                    &mut result.ty
+                } else if meta.path.is_ident("subject") {
+                    &mut result.subject
+                } else if meta.path.is_ident("source") {
+                    &mut result.source
+                } else {
+                    return Err(meta
+                        .error("unknown esdb attribute, expected `type`, `subject` or `source`"));
+                };
+                if slot.is_some() {
+                    return Err(meta.error("duplicate esdb attribute"));
+                }
+                *slot = Some(meta.value()?.parse()?);
+                Ok(())
+            })?;
+        }
+        Ok(result)
+    }
+
+    /// Use the attributes of the enum for everything that is not set on the variant.
+    fn with_fallback(self, fallback: &Self) -> Self {
+        Self {
+            ty: self.ty.or_else(|| fallback.ty.clone()),
+            subject: self.subject.or_else(|| fallback.subject.clone()),
+            source: self.source.or_else(|| fallback.source.clone()),
+        }
+    }
+
+    fn required(value: Option<&LitStr>, name: &str, span: &impl Spanned) -> syn::Result<LitStr> {
+        value.cloned().ok_or_else(|| {
+            syn::Error::new(
+                span.span(),
+                format!("missing `#[esdb({name} = \"...\")]` attribute"),
+            )
+        })
+    }
+}
+
+/// A subject template like `/books/{id}`, split into a format string and the referenced fields.
+struct SubjectTemplate {
+    format: LitStr,
+    placeholders: Vec<Member>,
+}
+
+impl SubjectTemplate {
+    fn parse(template: &LitStr) -> syn::Result<Self> {
+        let value = template.value();
+        let error = |message: &str| syn::Error::new(template.span(), message);
+        if !value.starts_with('/') {
+            return Err(error("the subject has to start with a `/`"));
+        }
+
+        let mut format = String::new();
+        let mut placeholders = Vec::new();
+        let mut rest = value.as_str();
+        while let Some(start) = rest.find(['{', '}']) {
+            if rest[start..].starts_with('}') {
+                return Err(error("unmatched `}` in subject template"));
+            }
+            format.push_str(&rest[..start]);
+            let after_start = &rest[start + 1..];
+            let end = after_start
+                .find('}')
+                .ok_or_else(|| error("unclosed `{` in subject template"))?;
+            let name = after_start[..end].trim();
+            let placeholder = if let Ok(index) = name.parse::<u32>() {
+                Member::Unnamed(Index {
+                    index,
+                    span: template.span(),
+                })
+            } else {
+                Member::Named(
+                    syn::parse_str::<Ident>(name)
+                        .map_err(|_| error("invalid field name in subject template"))?,
+                )
+            };
+            placeholders.push(placeholder);
+            format.push_str("{}");
+            rest = &after_start[end + 1..];
+        }
+        format.push_str(rest);
+
+        Ok(Self {
+            format: LitStr::new(&format, template.span()),
+            placeholders,
+        })
+    }
+
+    /// Make sure all placeholders reference existing fields.
+    fn validate(&self, fields: &Fields, template: &LitStr) -> syn::Result<()> {
+        for placeholder in &self.placeholders {
+            let exists = fields
+                .iter()
+                .enumerate()
+                .any(|(index, field)| match placeholder {
+                    Member::Named(name) => field.ident.as_ref() == Some(name),
+                    Member::Unnamed(unnamed) => unnamed.index as usize == index,
+                });
+            if !exists {
+                return Err(syn::Error::new(
+                    template.span(),
+                    format!(
+                        "the subject template references the unknown field `{}`",
+                        quote!(#placeholder)
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    match &input.data {
+        Data::Struct(data) => expand_struct(input, &data.fields),
+        Data::Enum(data) => expand_enum(input, data),
+        Data::Union(_) => Err(syn::Error::new(
+            input.span(),
+            "EventSourcingDbEvent can only be derived for structs and enums",
+        )),
+    }
+}
+
+fn expand_struct(input: &DeriveInput, fields: &Fields) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, constants_where_clause) = input.generics.split_for_impl();
+    // The data is (de)serialized with serde, so generic structs need the same bounds the serde derives infer.
+    let mut generics = input.generics.clone();
+    generics.make_where_clause().predicates.push(parse_quote! {
+        #name #ty_generics: ::eventsourcingdb::__private::serde::Serialize
+            + ::eventsourcingdb::__private::serde::de::DeserializeOwned
+    });
+    let where_clause = &generics.where_clause;
+    let attributes = EsdbAttributes::parse(&input.attrs)?;
+    let ty = EsdbAttributes::required(attributes.ty.as_ref(), "type", &input.ident)?;
+    let subject = EsdbAttributes::required(attributes.subject.as_ref(), "subject", &input.ident)?;
+    let source = EsdbAttributes::required(attributes.source.as_ref(), "source", &input.ident)?;
+
+    let template = SubjectTemplate::parse(&subject)?;
+    template.validate(fields, &subject)?;
+    let format = &template.format;
+    let placeholders = &template.placeholders;
+
+    let body = quote! {
+        const EVENT_TYPES: &'static [&'static str] = &[#ty];
+
+        fn event_type(&self) -> &'static str {
+            #ty
+        }
+
+        fn subject(&self) -> ::std::string::String {
+            ::std::format!(#format, #(self.#placeholders),*)
+        }
+
+        fn source(&self) -> &'static str {
+            #source
+        }
+
+        fn data(&self) -> ::std::result::Result<::eventsourcingdb::__private::serde_json::Value, ::eventsourcingdb::error::EventError> {
+            ::std::result::Result::Ok(::eventsourcingdb::__private::serde_json::to_value(self)?)
+        }
+
+        fn from_event(event: &::eventsourcingdb::event::Event) -> ::std::result::Result<Self, ::eventsourcingdb::error::EventError> {
+            if event.ty() != #ty {
+                return ::std::result::Result::Err(::eventsourcingdb::error::EventError::UnexpectedEventType(event.ty().to_string()));
+            }
+            event.data_as()
+        }
+    };
+
+    let constants = quote! {
+        #[automatically_derived]
+        impl #impl_generics #name #ty_generics #constants_where_clause {
+            /// The type of the event as stored in the DB.
+            pub const EVENT_TYPE: &'static str = #ty;
+            /// The template the subject of the event is built from.
+            pub const SUBJECT_TEMPLATE: &'static str = #subject;
+        }
+    };
+
+    Ok(quote! {
+        #constants
+        #[automatically_derived]
+        impl #impl_generics ::eventsourcingdb::event::EventSourcingDbEvent for #name #ty_generics #where_clause {
+            #body
+        }
+        #[automatically_derived]
+        impl #impl_generics ::std::convert::TryFrom<&::eventsourcingdb::event::Event> for #name #ty_generics #where_clause {
+            type Error = ::eventsourcingdb::error::EventError;
+
+            fn try_from(event: &::eventsourcingdb::event::Event) -> ::std::result::Result<Self, Self::Error> {
+                <Self as ::eventsourcingdb::event::EventSourcingDbEvent>::from_event(event)
+            }
+        }
+        #[automatically_derived]
+        impl #impl_generics ::std::convert::TryFrom<#name #ty_generics> for ::eventsourcingdb::event::EventCandidate #where_clause {
+            type Error = ::eventsourcingdb::__private::serde_json::Error;
+
+            fn try_from(value: #name #ty_generics) -> ::std::result::Result<Self, Self::Error> {
+                let data = ::eventsourcingdb::__private::serde_json::to_value(&value)?;
+                ::std::result::Result::Ok(::eventsourcingdb::event::EventCandidate::builder()
+                    .data(data)
+                    .source(::eventsourcingdb::event::EventSourcingDbEvent::source(&value))
+                    .subject(::eventsourcingdb::event::EventSourcingDbEvent::subject(&value))
+                    .ty(::eventsourcingdb::event::EventSourcingDbEvent::event_type(&value))
+                    .build())
+            }
+        }
+    })
+}
+
+/// The pieces of generated code for a single enum variant.
+struct VariantArms {
+    ty: LitStr,
+    event_type: TokenStream2,
+    subject: TokenStream2,
+    source: TokenStream2,
+    data: TokenStream2,
+    from_event: TokenStream2,
+}
+
+fn serde_attributes(attrs: &[Attribute]) -> Vec<&Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .collect()
+}
+
+/// The `#[serde(...)]` attributes of an enum or a variant that apply to the data of a variant with named fields.
+///
+/// The data of such a variant is serialized through a generated struct, so these attributes are forwarded to it.
+/// Attributes that only rename variants or choose the representation of the enum don't change the data of a variant and are ignored,
+/// just as serde does for the fields of a variant. All other attributes are rejected because they can't be honoured.
+#[derive(Default)]
+struct SerdeDataAttributes {
+    rename_all: Option<TokenStream2>,
+    deny_unknown_fields: bool,
+}
+
+impl SerdeDataAttributes {
+    fn parse_enum(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut result = Self::default();
+        for attr in serde_attributes(attrs) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename_all_fields") {
+                    result.rename_all = Some(Self::parse_rename_all(&meta)?);
+                    Ok(())
+                } else if ["rename", "rename_all", "tag", "content", "untagged", "crate", "expecting"]
+                    .iter()
+                    .any(|name| meta.path.is_ident(name))
+                {
+                    Self::skip_value(&meta)
+                } else {
+                    Err(meta.error("this serde attribute is not supported by EventSourcingDbEvent, set it on the variants instead"))
+                }
+            })?;
+        }
+        Ok(result)
+    }
+
+    fn parse_variant(variant: &Variant, fallback: &Self) -> syn::Result<Self> {
+        let has_named_fields = matches!(variant.fields, Fields::Named(_));
+        let mut result = Self {
+            rename_all: fallback.rename_all.clone(),
+            deny_unknown_fields: false,
+        };
+        for attr in serde_attributes(&variant.attrs) {
+            attr.parse_nested_meta(|meta| {
+                if has_named_fields && meta.path.is_ident("rename_all") {
+                    result.rename_all = Some(Self::parse_rename_all(&meta)?);
+                    Ok(())
+                } else if has_named_fields && meta.path.is_ident("deny_unknown_fields") {
+                    result.deny_unknown_fields = true;
+                    Ok(())
+                } else if meta.path.is_ident("rename") || meta.path.is_ident("alias") {
+                    Self::skip_value(&meta)
+                } else {
+                    Err(meta.error(
+                        "this serde attribute is not supported on variants by EventSourcingDbEvent",
+                    ))
+                }
+            })?;
+        }
+        Ok(result)
+    }
+
+    /// Parse the value of `rename_all`, which is either `= "..."` or `(serialize = "...", deserialize = "...")`.
+    fn parse_rename_all(meta: &ParseNestedMeta) -> syn::Result<TokenStream2> {
+        if meta.input.peek(Token![=]) {
+            let value: LitStr = meta.value()?.parse()?;
+            Ok(quote! { = #value })
+        } else {
+            let content;
+            let _ = parenthesized!(content in meta.input);
+            let tokens: TokenStream2 = content.parse()?;
+            Ok(quote! { (#tokens) })
+        }
+    }
+
+    fn skip_value(meta: &ParseNestedMeta) -> syn::Result<()> {
+        if meta.input.peek(Token![=]) {
+            let _: syn::Expr = meta.value()?.parse()?;
+        } else if meta.input.peek(Paren) {
+            let content;
+            let _ = parenthesized!(content in meta.input);
+            let _: TokenStream2 = content.parse()?;
+        }
+        Ok(())
+    }
+
+    fn to_attribute(&self) -> TokenStream2 {
+        let rename_all = self
+            .rename_all
+            .as_ref()
+            .map(|rename_all| quote! { rename_all #rename_all, });
+        let deny_unknown_fields = self
+            .deny_unknown_fields
+            .then(|| quote! { deny_unknown_fields, });
+        if rename_all.is_none() && deny_unknown_fields.is_none() {
+            return TokenStream2::new();
+        }
+        quote! { #[serde(#rename_all #deny_unknown_fields)] }
+    }
+}
+
+fn expand_variant(
+    enum_name: &Ident,
+    variant: &Variant,
+    fallback: &EsdbAttributes,
+    serde_fallback: &SerdeDataAttributes,
+) -> syn::Result<VariantArms> {
+    let name = &variant.ident;
+    let container_attributes =
+        SerdeDataAttributes::parse_variant(variant, serde_fallback)?.to_attribute();
+    let attributes = EsdbAttributes::parse(&variant.attrs)?.with_fallback(fallback);
+    let ty = EsdbAttributes::required(attributes.ty.as_ref(), "type", name)?;
+    let subject = EsdbAttributes::required(attributes.subject.as_ref(), "subject", name)?;
+    let source = EsdbAttributes::required(attributes.source.as_ref(), "source", name)?;
+    let template = SubjectTemplate::parse(&subject)?;
+    let format = &template.format;
+    let placeholders = &template.placeholders;
+
+    let event_type = quote! { Self::#name { .. } => #ty, };
+    let source = quote! { Self::#name { .. } => #source, };
+
+    let (subject, data, from_event) = match &variant.fields {
+        Fields::Named(fields) => {
+            template.validate(&variant.fields, &subject)?;
+            let field_names: Vec<_> = fields.named.iter().map(|field| &field.ident).collect();
+            let field_types: Vec<_> = fields.named.iter().map(|field| &field.ty).collect();
+            let field_attributes: Vec<_> = fields
+                .named
+                .iter()
+                .map(|field| serde_attributes(&field.attrs))
+                .collect();
+
+            let mut bindings: Vec<&Member> = Vec::new();
+            for placeholder in placeholders {
+                if !bindings.contains(&placeholder) {
+                    bindings.push(placeholder);
+                }
+            }
+            let subject = quote! {
+                Self::#name { #(#bindings,)* .. } => ::std::format!(#format, #(#placeholders),*),
+            };
+            let data = quote! {
+                #enum_name::#name { #(#field_names),* } => {
+                    #[derive(::eventsourcingdb::__private::serde::Serialize)]
+                    #[serde(crate = "::eventsourcingdb::__private::serde")]
+                    #container_attributes
+                    struct EsdbData<'esdb> {
+                        #(#(#field_attributes)* #field_names: &'esdb #field_types,)*
+                    }
+                    ::eventsourcingdb::__private::serde_json::to_value(&EsdbData { #(#field_names),* })
+                }
+            };
+            let from_event = quote! {
+                #ty => {
+                    #[derive(::eventsourcingdb::__private::serde::Deserialize)]
+                    #[serde(crate = "::eventsourcingdb::__private::serde")]
+                    #container_attributes
+                    struct EsdbData {
+                        #(#(#field_attributes)* #field_names: #field_types,)*
+                    }
+                    let data: EsdbData = event.data_as()?;
+                    ::std::result::Result::Ok(Self::#name { #(#field_names: data.#field_names),* })
+                }
+            };
+            (subject, data, from_event)
+        }
+        // The placeholders reference the fields of the wrapped type, which can't be checked here.
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            let subject = quote! {
+                Self::#name(inner) => ::std::format!(#format, #(inner.#placeholders),*),
+            };
+            let data = quote! {
+                #enum_name::#name(inner) => ::eventsourcingdb::__private::serde_json::to_value(inner),
+            };
+            let from_event = quote! {
+                #ty => ::std::result::Result::Ok(Self::#name(event.data_as()?)),
+            };
+            (subject, data, from_event)
+        }
+        Fields::Unnamed(_) => {
+            return Err(syn::Error::new(
+                variant.span(),
+                "tuple variants must have exactly one field",
+            ));
+        }
+        Fields::Unit => {
+            template.validate(&variant.fields, &subject)?;
+            let subject = quote! {
+                Self::#name => ::std::string::String::from(#format),
+            };
+            let data = quote! {
+                #enum_name::#name => ::std::result::Result::Ok(
+                    ::eventsourcingdb::__private::serde_json::Value::Object(::eventsourcingdb::__private::serde_json::Map::new())
+                ),
+            };
+            let from_event = quote! {
+                #ty => ::std::result::Result::Ok(Self::#name),
+            };
+            (subject, data, from_event)
+        }
+    };
+
+    Ok(VariantArms {
+        ty,
+        event_type,
+        subject,
+        source,
+        data,
+        from_event,
+    })
+}
+
+fn expand_enum(input: &DeriveInput, data: &DataEnum) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "EventSourcingDbEvent can not be derived for generic enums",
+        ));
+    }
+    if data.variants.is_empty() {
+        return Err(syn::Error::new(
+            input.span(),
+            "EventSourcingDbEvent can not be derived for enums without variants",
+        ));
+    }
+    let fallback = EsdbAttributes::parse(&input.attrs)?;
+    let serde_fallback = SerdeDataAttributes::parse_enum(&input.attrs)?;
+    if let Some(ty) = &fallback.ty {
+        return Err(syn::Error::new(
+            ty.span(),
+            "the `type` of an enum has to be set on each variant",
+        ));
+    }
+
+    let variants = data
+        .variants
+        .iter()
+        .map(|variant| expand_variant(name, variant, &fallback, &serde_fallback))
+        .collect::<syn::Result<Vec<_>>>()?;
+    for (index, variant) in variants.iter().enumerate() {
+        if variants[..index]
+            .iter()
+            .any(|other| other.ty.value() == variant.ty.value())
+        {
+            return Err(syn::Error::new(
+                variant.ty.span(),
+                "the event type is used by more than one variant",
+            ));
+        }
+    }
+
+    let types = variants.iter().map(|variant| &variant.ty);
+    let event_types = variants.iter().map(|variant| &variant.event_type);
+    let subjects = variants.iter().map(|variant| &variant.subject);
+    let sources = variants.iter().map(|variant| &variant.source);
+    let data: Vec<_> = variants.iter().map(|variant| &variant.data).collect();
+    let from_events = variants.iter().map(|variant| &variant.from_event);
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl ::eventsourcingdb::event::EventSourcingDbEvent for #name {
+            const EVENT_TYPES: &'static [&'static str] = &[#(#types),*];
+
+            fn event_type(&self) -> &'static str {
+                match self {
+                    #(#event_types)*
+                }
+            }
+
+            fn subject(&self) -> ::std::string::String {
+                match self {
+                    #(#subjects)*
+                }
+            }
+
+            fn source(&self) -> &'static str {
+                match self {
+                    #(#sources)*
+                }
+            }
+
+            fn data(&self) -> ::std::result::Result<::eventsourcingdb::__private::serde_json::Value, ::eventsourcingdb::error::EventError> {
+                let data = match self {
+                    #(#data)*
+                };
+                ::std::result::Result::Ok(data?)
+            }
+
+            fn from_event(event: &::eventsourcingdb::event::Event) -> ::std::result::Result<Self, ::eventsourcingdb::error::EventError> {
+                match event.ty() {
+                    #(#from_events)*
+                    other => ::std::result::Result::Err(::eventsourcingdb::error::EventError::UnexpectedEventType(other.to_string())),
+                }
+            }
+        }
+        #[automatically_derived]
+        impl ::std::convert::TryFrom<&::eventsourcingdb::event::Event> for #name {
+            type Error = ::eventsourcingdb::error::EventError;
+
+            fn try_from(event: &::eventsourcingdb::event::Event) -> ::std::result::Result<Self, Self::Error> {
+                <Self as ::eventsourcingdb::event::EventSourcingDbEvent>::from_event(event)
+            }
+        }
+        #[automatically_derived]
+        impl ::std::convert::TryFrom<#name> for ::eventsourcingdb::event::EventCandidate {
+            type Error = ::eventsourcingdb::__private::serde_json::Error;
+
+            fn try_from(value: #name) -> ::std::result::Result<Self, Self::Error> {
+                let data = match &value {
+                    #(#data)*
+                }?;
+                ::std::result::Result::Ok(::eventsourcingdb::event::EventCandidate::builder()
+                    .data(data)
+                    .source(::eventsourcingdb::event::EventSourcingDbEvent::source(&value))
+                    .subject(::eventsourcingdb::event::EventSourcingDbEvent::subject(&value))
+                    .ty(::eventsourcingdb::event::EventSourcingDbEvent::event_type(&value))
+                    .build())
+            }
+        }
+    })
+}
@@ -0,0 +1,360 @@
+//! Aggregates rebuilt from the events of a subject.
+//!
+//! An [`Aggregate`] is the state of a subject, folded from its events. It decides which new events to write in response to a command.
+//! The [`Repository`] loads aggregates from the DB and writes their new events with a precondition that ensures no other events
+//! have been written to the subject in the meantime.
+//! For subjects with many events, the [`SnapshotRepository`] loads aggregates from a [`SnapshotStore`] and only replays the events written since.
+//! ```
+//! use eventsourcingdb::{
+//!     aggregate::{Aggregate, Repository},
+//!     error::EventError,
+//!     event::{Event, EventCandidate},
+//! };
+//! # use serde_json::json;
+//! #[derive(Default)]
+//! struct Book {
+//!     borrowed: bool,
+//! }
+//!
+//! enum BookCommand {
+//!     Borrow,
+//! }
+//!
+//! #[derive(Debug)]
+//! struct AlreadyBorrowed;
+//!
+//! impl Aggregate for Book {
+//!     type Command = BookCommand;
+//!     type Error = AlreadyBorrowed;
+//!
+//!     fn apply(&mut self, event: &Event) -> Result<(), EventError> {
+//!         match event.ty() {
+//!             "io.eventsourcingdb.library.book-borrowed" => self.borrowed = true,
+//!             "io.eventsourcingdb.library.book-returned" => self.borrowed = false,
+//!             _ => {}
+//!         }
+//!         Ok(())
+//!     }
+//!
+//!     fn handle(&self, subject: &str, command: BookCommand) -> Result<Vec<EventCandidate>, AlreadyBorrowed> {
+//!         match command {
+//!             BookCommand::Borrow if self.borrowed => Err(AlreadyBorrowed),
+//!             BookCommand::Borrow => Ok(vec![
+//!                 EventCandidate::builder()
+//!                     .source("https://library.eventsourcingdb.io".to_string())
+//!                     .subject(subject.to_string())
+//!                     .ty("io.eventsourcingdb.library.book-borrowed".to_string())
+//!                     .data(json!({}))
+//!                     .build(),
+//!             ]),
+//!         }
+//!     }
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! # let container = eventsourcingdb::container::Container::start_preview().await.unwrap();
+//! let db_url = "http://localhost:3000/";
+//! let api_token = "secrettoken";
+//! # let db_url = container.get_base_url().await.unwrap();
+//! # let api_token = container.get_api_token();
+//! let client = eventsourcingdb::client::Client::new(db_url, api_token);
+//! let repository = Repository::<Book>::new(client);
+//! let mut book = repository.load("/books/42").await.expect("Failed to load book");
+//! let events = book.handle(BookCommand::Borrow).expect("Book is already borrowed");
+//! repository.commit(&mut book, events).await.expect("Failed to commit events");
+//! assert!(book.state().borrowed);
+//! # })
+//! ```
+
+mod snapshot_repository;
+mod snapshot_store;
+
+use std::marker::PhantomData;
+
+use futures::StreamExt;
+
+pub use snapshot_repository::SnapshotRepository;
+pub use snapshot_store::{FileSnapshotStore, InMemorySnapshotStore, Snapshot, SnapshotStore};
+
+use crate::{
+    Precondition,
+    client::Client,
+    error::{ClientError, CommandError, EventError},
+    event::{Event, EventCandidate},
+    request_options::{Bound, BoundType, ReadEventsOptions},
+};
+
+/// Represents the state of a subject that is rebuilt from its events.
+pub trait Aggregate: Default {
+    /// The commands the aggregate handles.
+    type Command;
+    /// The error returned if the aggregate rejects a command.
+    type Error;
+
+    /// Apply an event of the subject to the state.
+    ///
+    /// # Errors
+    /// Returns an error if the event can't be applied, e.g. because its data does not match the expected type.
+    fn apply(&mut self, event: &Event) -> Result<(), EventError>;
+
+    /// Decide which events to write in response to a command, based on the current state.
+    ///
+    /// The events are not applied to the state until they have been written.
+    ///
+    /// # Errors
+    /// Returns an error if the command is rejected in the current state.
+    fn handle(
+        &self,
+        subject: &str,
+        command: Self::Command,
+    ) -> Result<Vec<EventCandidate>, Self::Error>;
+}
+
+/// An aggregate loaded from the DB, together with the subject it belongs to and the last event applied to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AggregateRoot<A> {
+    subject: String,
+    state: A,
+    last_event_id: Option<String>,
+    events_since_snapshot: u64,
+}
+
+impl<A: Aggregate> AggregateRoot<A> {
+    /// Create an aggregate for a subject with the given state, which has been rebuilt up to the given event.
+    ///
+    /// Use this to continue from a previously saved state instead of replaying all events of the subject.
+    #[must_use]
+    pub fn from_state(subject: impl Into<String>, state: A, last_event_id: Option<String>) -> Self {
+        Self {
+            subject: subject.into(),
+            state,
+            last_event_id,
+            events_since_snapshot: 0,
+        }
+    }
+    /// Get the subject of the aggregate.
+    #[must_use]
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+    /// Get the current state of the aggregate.
+    #[must_use]
+    pub fn state(&self) -> &A {
+        &self.state
+    }
+    /// Take the current state out of the aggregate.
+    #[must_use]
+    pub fn into_state(self) -> A {
+        self.state
+    }
+    /// Get the ID of the last event applied to the aggregate.
+    ///
+    /// This is `None` if the subject has no events yet.
+    #[must_use]
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.last_event_id.as_deref()
+    }
+    /// Get the number of events applied to the aggregate since it was created from its state.
+    ///
+    /// This is used to decide when to take a snapshot, see [`SnapshotRepository`].
+    #[must_use]
+    pub fn events_since_snapshot(&self) -> u64 {
+        self.events_since_snapshot
+    }
+    /// Let the aggregate decide which events to write in response to a command.
+    ///
+    /// # Errors
+    /// Returns an error if the aggregate rejects the command.
+    pub fn handle(&self, command: A::Command) -> Result<Vec<EventCandidate>, A::Error> {
+        self.state.handle(&self.subject, command)
+    }
+    /// Get the precondition that ensures the subject has not changed since the aggregate was loaded.
+    #[must_use]
+    pub fn precondition(&self) -> Precondition {
+        match &self.last_event_id {
+            Some(event_id) => Precondition::IsSubjectOnEventId {
+                subject: self.subject.clone(),
+                event_id: event_id.clone(),
+            },
+            None => Precondition::IsSubjectPristine {
+                subject: self.subject.clone(),
+            },
+        }
+    }
+    /// Apply an event to the aggregate and remember it as the last event.
+    ///
+    /// Events of other subjects are ignored.
+    ///
+    /// # Errors
+    /// Returns an error if the aggregate can't apply the event.
+    pub fn apply(&mut self, event: &Event) -> Result<(), EventError> {
+        if event.subject() != self.subject {
+            return Ok(());
+        }
+        self.state.apply(event)?;
+        self.last_event_id = Some(event.id().to_string());
+        self.events_since_snapshot += 1;
+        Ok(())
+    }
+}
+
+/// Loads aggregates from the DB and writes their new events.
+///
+/// Writing uses the precondition of the [`AggregateRoot`], so the write fails if other events were written to the subject after it was loaded.
+#[derive(Debug, Clone)]
+pub struct Repository<A> {
+    client: Client,
+    max_conflict_retries: u32,
+    aggregate: PhantomData<fn() -> A>,
+}
+
+impl<A: Aggregate> Repository<A> {
+    /// Create a repository that uses the given client.
+    #[must_use]
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            max_conflict_retries: 3,
+            aggregate: PhantomData,
+        }
+    }
+
+    /// Set the number of times a command is retried if it conflicts with concurrent writes.
+    ///
+    /// By default, a command is retried 3 times, see [`Repository::execute_command`].
+    #[must_use]
+    pub fn with_max_conflict_retries(mut self, max_conflict_retries: u32) -> Self {
+        self.max_conflict_retries = max_conflict_retries;
+        self
+    }
+
+    /// Get the client of the repository.
+    #[must_use]
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Get the number of times a command is retried if it conflicts with concurrent writes.
+    #[must_use]
+    pub fn max_conflict_retries(&self) -> u32 {
+        self.max_conflict_retries
+    }
+
+    /// Load an aggregate by replaying all events of its subject.
+    ///
+    /// Events of nested subjects are not included. If the subject has no events, the aggregate has its default state.
+    ///
+    /// # Errors
+    /// Returns an error if reading the events fails or an event can't be applied.
+    pub async fn load(&self, subject: &str) -> Result<AggregateRoot<A>, ClientError> {
+        self.catch_up(AggregateRoot::from_state(subject, A::default(), None))
+            .await
+    }
+
+    /// Apply all events written to the subject of an aggregate after its last event.
+    ///
+    /// # Errors
+    /// Returns an error if reading the events fails or an event can't be applied.
+    pub async fn catch_up(
+        &self,
+        mut aggregate: AggregateRoot<A>,
+    ) -> Result<AggregateRoot<A>, ClientError> {
+        let subject = aggregate.subject.clone();
+        let last_event_id = aggregate.last_event_id.clone();
+        let options = last_event_id.as_deref().map(|id| ReadEventsOptions {
+            lower_bound: Some(Bound {
+                bound_type: BoundType::Exclusive,
+                id,
+            }),
+            ..Default::default()
+        });
+        let mut events = self.client.read_events(&subject, options).await?;
+        while let Some(event) = events.next().await {
+            aggregate.apply(&event?)?;
+        }
+        Ok(aggregate)
+    }
+
+    /// Write new events of an aggregate and apply them to it.
+    ///
+    /// The write fails with an error for which [`ClientError::is_precondition_failed`] is true
+    /// if other events were written to the subject since the aggregate was loaded.
+    ///
+    /// # Errors
+    /// Returns an error if writing the events fails or a written event can't be applied.
+    pub async fn commit(
+        &self,
+        aggregate: &mut AggregateRoot<A>,
+        events: Vec<EventCandidate>,
+    ) -> Result<Vec<Event>, ClientError> {
+        if events.is_empty() {
+            return Ok(Vec::new());
+        }
+        let written = self
+            .client
+            .write_events(events, vec![aggregate.precondition()])
+            .await?;
+        for event in &written {
+            aggregate.apply(event)?;
+        }
+        Ok(written)
+    }
+
+    /// Load an aggregate, decide which events to write and commit them, retrying if the subject changed in the meantime.
+    ///
+    /// If the commit fails because other events were written to the subject after it was loaded,
+    /// the aggregate catches up with these events and `decide` runs again on the new state.
+    /// This is repeated up to [`Repository::max_conflict_retries`] times.
+    /// ```
+    /// # use eventsourcingdb::{aggregate::{Aggregate, Repository}, error::EventError, event::{Event, EventCandidate}};
+    /// # #[derive(Default)]
+    /// # struct Book;
+    /// # impl Aggregate for Book {
+    /// #     type Command = ();
+    /// #     type Error = ();
+    /// #     fn apply(&mut self, _event: &Event) -> Result<(), EventError> { Ok(()) }
+    /// #     fn handle(&self, _subject: &str, _command: ()) -> Result<Vec<EventCandidate>, ()> { Ok(vec![]) }
+    /// # }
+    /// # tokio_test::block_on(async {
+    /// # let container = eventsourcingdb::container::Container::start_preview().await.unwrap();
+    /// # let client = container.get_client().await.unwrap();
+    /// let repository = Repository::<Book>::new(client).with_max_conflict_retries(5);
+    /// let written = repository
+    ///     .execute_command("/books/42", |book| book.handle(()))
+    ///     .await
+    ///     .expect("Failed to execute command");
+    /// # assert!(written.is_empty());
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`CommandError::Rejected`] if `decide` fails, [`CommandError::Conflict`] if the retries are used up,
+    /// and [`CommandError::ClientError`] if reading or writing the events fails for any other reason.
+    pub async fn execute_command<E, F>(
+        &self,
+        subject: &str,
+        mut decide: F,
+    ) -> Result<Vec<Event>, CommandError<E>>
+    where
+        F: FnMut(&AggregateRoot<A>) -> Result<Vec<EventCandidate>, E>,
+    {
+        let mut aggregate = self.load(subject).await?;
+        let mut attempt = 1;
+        loop {
+            let events = decide(&aggregate).map_err(CommandError::Rejected)?;
+            match self.commit(&mut aggregate, events).await {
+                Err(error) if error.is_precondition_failed() => {
+                    if attempt > self.max_conflict_retries {
+                        return Err(CommandError::Conflict {
+                            subject: subject.to_string(),
+                            attempts: attempt,
+                        });
+                    }
+                    aggregate = self.catch_up(aggregate).await?;
+                    attempt += 1;
+                }
+                result => return Ok(result?),
+            }
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use serde::{Serialize, de::DeserializeOwned};
+
+use super::{Aggregate, AggregateRoot, Repository, Snapshot, SnapshotStore};
+use crate::{
+    error::ClientError,
+    event::{Event, EventCandidate},
+};
+
+/// Loads aggregates from their latest snapshot and only replays the events written after it.
+///
+/// A new snapshot is saved whenever at least [`SnapshotRepository::snapshot_frequency`] events were applied
+/// to an aggregate since its last snapshot, either while loading it or while committing its events.
+/// ```
+/// use eventsourcingdb::aggregate::{Aggregate, InMemorySnapshotStore, Repository, SnapshotRepository};
+/// # use eventsourcingdb::{error::EventError, event::{Event, EventCandidate}};
+/// #[derive(Default, serde::Serialize, serde::Deserialize)]
+/// struct Book {
+///     borrowed: bool,
+/// }
+/// # impl Aggregate for Book {
+/// #     type Command = ();
+/// #     type Error = ();
+/// #     fn apply(&mut self, _event: &Event) -> Result<(), EventError> { Ok(()) }
+/// #     fn handle(&self, _subject: &str, _command: ()) -> Result<Vec<EventCandidate>, ()> { Ok(vec![]) }
+/// # }
+/// # tokio_test::block_on(async {
+/// # let container = eventsourcingdb::container::Container::start_preview().await.unwrap();
+/// # let client = container.get_client().await.unwrap();
+/// let repository = SnapshotRepository::new(Repository::<Book>::new(client), InMemorySnapshotStore::new())
+///     .with_snapshot_frequency(50);
+/// let book = repository.load("/books/42").await.expect("Failed to load book");
+/// # })
+/// ```
+#[derive(Debug)]
+pub struct SnapshotRepository<A, S> {
+    repository: Repository<A>,
+    store: S,
+    snapshot_frequency: u64,
+}
+
+impl<A, S> SnapshotRepository<A, S>
+where
+    A: Aggregate + Serialize + DeserializeOwned,
+    S: SnapshotStore,
+{
+    /// Create a repository that keeps the snapshots of its aggregates in the given store.
+    ///
+    /// By default, a snapshot is saved every 100 events.
+    #[must_use]
+    pub fn new(repository: Repository<A>, store: S) -> Self {
+        Self {
+            repository,
+            store,
+            snapshot_frequency: 100,
+        }
+    }
+
+    /// Set the number of events after which a new snapshot is saved.
+    #[must_use]
+    pub fn with_snapshot_frequency(mut self, snapshot_frequency: u64) -> Self {
+        self.snapshot_frequency = snapshot_frequency;
+        self
+    }
+
+    /// Get the underlying repository.
+    #[must_use]
+    pub fn repository(&self) -> &Repository<A> {
+        &self.repository
+    }
+
+    /// Get the store the snapshots are kept in.
+    #[must_use]
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Get the number of events after which a new snapshot is saved.
+    #[must_use]
+    pub fn snapshot_frequency(&self) -> u64 {
+        self.snapshot_frequency
+    }
+
+    /// Load an aggregate from its latest snapshot and apply the events written to its subject after it.
+    ///
+    /// If there is no snapshot, all events of the subject are replayed.
+    ///
+    /// # Errors
+    /// Returns an error if the snapshot can't be read or deserialized, reading the events fails or an event can't be applied.
+    pub async fn load(&self, subject: &str) -> Result<AggregateRoot<A>, ClientError> {
+        let aggregate = match self.store.load(subject).await? {
+            Some(snapshot) => AggregateRoot::from_state(
+                subject,
+                serde_json::from_value(snapshot.state)?,
+                Some(snapshot.last_event_id),
+            ),
+            None => AggregateRoot::from_state(subject, A::default(), None),
+        };
+        let mut aggregate = self.repository.catch_up(aggregate).await?;
+        self.save_snapshot_if_due(&mut aggregate).await?;
+        Ok(aggregate)
+    }
+
+    /// Write new events of an aggregate and apply them to it, see [`Repository::commit`].
+    ///
+    /// # Errors
+    /// Returns an error if writing the events fails, a written event can't be applied or the snapshot can't be saved.
+    pub async fn commit(
+        &self,
+        aggregate: &mut AggregateRoot<A>,
+        events: Vec<EventCandidate>,
+    ) -> Result<Vec<Event>, ClientError> {
+        let written = self.repository.commit(aggregate, events).await?;
+        self.save_snapshot_if_due(aggregate).await?;
+        Ok(written)
+    }
+
+    /// Save a snapshot of an aggregate, regardless of the snapshot frequency.
+    ///
+    /// Aggregates without events are not saved.
+    ///
+    /// # Errors
+    /// Returns an error if the state can't be serialized or the snapshot can't be saved.
+    pub async fn save_snapshot(&self, aggregate: &mut AggregateRoot<A>) -> Result<(), ClientError> {
+        let Some(last_event_id) = aggregate.last_event_id.clone() else {
+            return Ok(());
+        };
+        let snapshot = Snapshot {
+            subject: aggregate.subject.clone(),
+            last_event_id,
+            state: serde_json::to_value(&aggregate.state)?,
+        };
+        self.store.save(&snapshot).await?;
+        aggregate.events_since_snapshot = 0;
+        Ok(())
+    }
+
+    /// Utility function to save a snapshot once enough events were applied since the last one.
+    async fn save_snapshot_if_due(
+        &self,
+        aggregate: &mut AggregateRoot<A>,
+    ) -> Result<(), ClientError> {
+        if aggregate.events_since_snapshot >= self.snapshot_frequency.max(1) {
+            self.save_snapshot(aggregate).await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    future::Future,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::{Mutex, PoisonError},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{error::ClientError, file_store};
+
+/// The state of a subject at a specific event, stored to avoid replaying all events of the subject.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Snapshot {
+    /// The subject the state belongs to
+    pub subject: String,
+    /// The ID of the last event applied to the state
+    pub last_event_id: String,
+    /// The serialized state
+    pub state: Value,
+}
+
+/// Stores the latest snapshot of each subject.
+///
+/// Implement this trait to keep snapshots in a database or cache of your choice.
+pub trait SnapshotStore: Debug + Send + Sync {
+    /// Load the latest snapshot of a subject, or `None` if there is none.
+    ///
+    /// # Errors
+    /// Returns an error if the snapshot can't be read.
+    fn load(
+        &self,
+        subject: &str,
+    ) -> impl Future<Output = Result<Option<Snapshot>, ClientError>> + Send;
+
+    /// Store a snapshot, replacing the previous snapshot of its subject.
+    ///
+    /// # Errors
+    /// Returns an error if the snapshot can't be written.
+    fn save(&self, snapshot: &Snapshot) -> impl Future<Output = Result<(), ClientError>> + Send;
+}
+
+/// A snapshot store backed by a map, suited for tests and for processes that can afford to replay events after a restart.
+#[derive(Debug, Default)]
+pub struct InMemorySnapshotStore {
+    snapshots: Mutex<HashMap<String, Snapshot>>,
+}
+
+impl InMemorySnapshotStore {
+    /// Create an empty store.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SnapshotStore for InMemorySnapshotStore {
+    async fn load(&self, subject: &str) -> Result<Option<Snapshot>, ClientError> {
+        Ok(self
+            .snapshots
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(subject)
+            .cloned())
+    }
+
+    async fn save(&self, snapshot: &Snapshot) -> Result<(), ClientError> {
+        let _ = self
+            .snapshots
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(snapshot.subject.clone(), snapshot.clone());
+        Ok(())
+    }
+}
+
+/// Persists the latest snapshot of each subject as a JSON file.
+///
+/// Saving replaces the file atomically and flushes it to disk, so loading finds either the previous or the new snapshot,
+/// even after a power loss. Missing directories are created on demand.
+#[derive(Debug, Clone)]
+pub struct FileSnapshotStore {
+    directory: PathBuf,
+}
+
+impl FileSnapshotStore {
+    /// Create a store that keeps its files in the given directory.
+    #[must_use]
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    /// Get the directory the store keeps its files in.
+    #[must_use]
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Utility function to get the file of a subject.
+    ///
+    /// The file name is derived from a hash of the subject, while the snapshot inside the file still names its subject.
+    fn path(&self, subject: &str) -> PathBuf {
+        file_store::key_path(&self.directory, subject, "json")
+    }
+}
+
+impl SnapshotStore for FileSnapshotStore {
+    async fn load(&self, subject: &str) -> Result<Option<Snapshot>, ClientError> {
+        match tokio::fs::read(self.path(subject)).await {
+            Ok(content) => {
+                let snapshot: Snapshot = serde_json::from_slice(&content)?;
+                Ok((snapshot.subject == subject).then_some(snapshot))
+            }
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    async fn save(&self, snapshot: &Snapshot) -> Result<(), ClientError> {
+        let content = serde_json::to_vec(snapshot)?;
+        file_store::write_atomically(&self.path(&snapshot.subject), &content).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+//! Audits that prove the events in the DB have not been tampered with.
+//!
+//! An audit reads all events of the DB and checks the hash of every event, that every event links to the hash of the event
+//! written before it and, if a verifying key is given, the signature of every event. Unlike [`crate::event::EventStreamExt::verify_chain`],
+//! the audit does not stop at the first failure but collects all of them in an [`AuditReport`], which can be serialized to JSON.
+//! The signature verification of the client is bypassed for the same reason, so invalid signatures end up in the report as well.
+//! ```
+//! # tokio_test::block_on(async {
+//! # let container = eventsourcingdb::container::Container::start_preview().await.unwrap();
+//! let db_url = "http://localhost:3000/";
+//! let api_token = "secrettoken";
+//! # let db_url = container.get_base_url().await.unwrap();
+//! # let api_token = container.get_api_token();
+//! let client = eventsourcingdb::client::Client::new(db_url, api_token);
+//! let report = eventsourcingdb::audit::audit(&client, None).await.expect("Failed to audit events");
+//! println!("{}", serde_json::to_string_pretty(&report).expect("Failed to serialize report"));
+//! assert!(report.is_valid());
+//! # })
+//! ```
+
+use chrono::{DateTime, Utc};
+use ed25519_dalek::VerifyingKey;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::Client,
+    error::{ClientError, EventError},
+    event::{ChainVerifier, Event},
+    request_options::ReadEventsOptions,
+};
+
+/// The result of an audit.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditReport {
+    /// The number of audited events
+    pub event_count: u64,
+    /// The number of audited events that have a signature
+    pub signed_event_count: u64,
+    /// Whether the signatures were verified
+    pub signatures_verified: bool,
+    /// The ID of the first event
+    pub first_event_id: Option<String>,
+    /// The ID of the last event
+    pub last_event_id: Option<String>,
+    /// The time of the first event
+    pub first_event_time: Option<DateTime<Utc>>,
+    /// The time of the last event
+    pub last_event_time: Option<DateTime<Utc>>,
+    /// The time the audit started
+    pub started_at: DateTime<Utc>,
+    /// The time the audit finished
+    pub finished_at: DateTime<Utc>,
+    /// The events that failed a check
+    pub failures: Vec<AuditFailure>,
+}
+
+/// An event that failed a check during an audit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditFailure {
+    /// The ID of the offending event
+    pub event_id: String,
+    /// The check the event failed
+    pub kind: AuditFailureKind,
+    /// A description of the failure
+    pub message: String,
+}
+
+/// The checks an event can fail during an audit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum AuditFailureKind {
+    /// The hash of the event does not match its content
+    InvalidHash,
+    /// The predecessor hash of the event does not match the hash of the event before it
+    BrokenChain,
+    /// The event has no signature
+    MissingSignature,
+    /// The signature of the event is malformed or invalid
+    InvalidSignature,
+}
+
+impl AuditReport {
+    /// Whether all events passed all checks.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Utility function to check an event and add it to the report.
+    fn audit_event(
+        &mut self,
+        event: &Event,
+        chain: &mut ChainVerifier,
+        verifying_key: Option<&VerifyingKey>,
+    ) {
+        self.event_count += 1;
+        if event.signature().is_some() {
+            self.signed_event_count += 1;
+        }
+        if self.first_event_id.is_none() {
+            self.first_event_id = Some(event.id().to_string());
+            self.first_event_time = Some(*event.time());
+        }
+        self.last_event_id = Some(event.id().to_string());
+        self.last_event_time = Some(*event.time());
+
+        if let Err(error) = ChainVerifier::check_hash(event) {
+            self.add_failure(event, AuditFailureKind::InvalidHash, &error);
+        }
+        if let Err(error) = chain.check_link(event) {
+            self.add_failure(event, AuditFailureKind::BrokenChain, &error);
+        }
+        chain.advance(event);
+
+        if let Some(verifying_key) = verifying_key {
+            match event.verify_signature(verifying_key) {
+                Ok(()) | Err(EventError::HashVerificationFailed { .. }) => {}
+                Err(error @ EventError::MissingSignature) => {
+                    self.add_failure(event, AuditFailureKind::MissingSignature, &error);
+                }
+                Err(error) => self.add_failure(event, AuditFailureKind::InvalidSignature, &error),
+            }
+        }
+    }
+
+    /// Utility function to add a failure to the report.
+    fn add_failure(&mut self, event: &Event, kind: AuditFailureKind, error: &impl ToString) {
+        self.failures.push(AuditFailure {
+            event_id: event.id().to_string(),
+            kind,
+            message: error.to_string(),
+        });
+    }
+}
+
+/// Audit all events in the DB.
+///
+/// If a verifying key is given, every event must have a valid signature. Otherwise, the key of the
+/// [signature verification](crate::client::Client::get_signature_verification) of the client is used, if any.
+///
+/// # Errors
+/// Returns an error if the events can't be read. Events that fail a check are not errors, they are listed in the report.
+pub async fn audit(
+    client: &Client,
+    verifying_key: Option<&VerifyingKey>,
+) -> Result<AuditReport, ClientError> {
+    let verifying_key = verifying_key.or_else(|| {
+        client
+            .get_signature_verification()
+            .map(|signature_verification| &signature_verification.verifying_key)
+    });
+    let mut report = AuditReport {
+        signatures_verified: verifying_key.is_some(),
+        started_at: Utc::now(),
+        ..Default::default()
+    };
+    let options = ReadEventsOptions {
+        recursive: true,
+        ..Default::default()
+    };
+    let mut events = client.read_events_unverified("/", Some(options)).await?;
+    let mut chain = ChainVerifier::from_genesis();
+    while let Some(event) = events.next().await {
+        report.audit_event(&event?, &mut chain, verifying_key);
+    }
+    report.finished_at = Utc::now();
+    Ok(report)
+}
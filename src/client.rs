@@ -18,31 +18,110 @@
 //! If this works, it means that the client is correctly configured and you can use it to make requests to the DB.
 
 mod client_request;
+#[cfg(feature = "schemars")]
+mod event_schema;
+mod event_schema_cache;
+mod observed_events;
 mod precondition;
 pub mod request_options;
+mod resilient_observe;
+mod retry_policy;
+mod subscription;
 
 use crate::{
     client::client_request::ReadEventTypeRequest,
-    error::ClientError,
-    event::{Event, EventCandidate, ManagementEvent},
+    error::{ClientError, RowDeserializationError, ServerError},
+    event::{
+        Event, EventCandidate, EventStreamExt, ManagementEvent, SignatureVerification, TypedEvent,
+    },
     request_options::EventType,
+    subject::{Subject, SubjectTree},
 };
 use client_request::{
-    ClientRequest, ListEventTypesRequest, ListSubjectsRequest, ObserveEventsRequest,
-    OneShotRequest, PingRequest, ReadEventsRequest, RegisterEventSchemaRequest,
-    RunEventqlQueryRequest, StreamingRequest, VerifyApiTokenRequest, WriteEventsRequest,
+    ClientRequest, HeartbeatWatchdog, ListEventTypesRequest, ListSubjectsRequest,
+    ObserveEventsRequest, OneShotRequest, PingRequest, ReadEventsRequest,
+    RegisterEventSchemaRequest, RunEventqlQueryRequest, StreamingRequest, VerifyApiTokenRequest,
+    WriteEventsRequest,
 };
-use futures::Stream;
-pub use precondition::Precondition;
+#[cfg(feature = "schemars")]
+pub use event_schema::{EventSchemaComparison, event_schema_for};
+use event_schema_cache::EventSchemaCache;
+use futures::{Stream, StreamExt, stream::BoxStream};
+pub use observed_events::ObservedEvents;
+pub use precondition::{Precondition, Preconditions};
 use reqwest;
+use resilient_observe::ResilientObservation;
+pub use retry_policy::RetryPolicy;
+use serde::de::DeserializeOwned;
+use std::{future::Future, sync::Arc, time::Duration};
+use subscription::Subscription;
+pub use subscription::SubscriptionItem;
+use typed_builder::TypedBuilder;
 use url::Url;
 
 /// Client for an [EventsourcingDB](https://www.eventsourcingdb.io/) instance.
-#[derive(Debug)]
+///
+/// Use [`Client::new`] to create a client with the default settings or [`Client::builder`] to configure it.
+/// ```
+/// # use std::time::Duration;
+/// # use eventsourcingdb::client::Client;
+/// let http_client = reqwest::Client::builder()
+///     .connect_timeout(Duration::from_secs(2))
+///     .user_agent("my-service/1.0")
+///     .build()
+///     .expect("Failed to build HTTP client");
+/// let client = Client::builder()
+///     .base_url("http://localhost:3000/".parse().unwrap())
+///     .api_token("secrettoken")
+///     .reqwest(http_client)
+///     .timeout(Duration::from_secs(10))
+///     .build();
+/// ```
+#[derive(Debug, Clone, TypedBuilder)]
 pub struct Client {
+    /// The base URL of the DB instance.
     base_url: Url,
+    /// The API token to authenticate with.
+    #[builder(setter(into))]
     api_token: String,
+    /// A preconfigured HTTP client.
+    ///
+    /// Use this to configure connect timeouts, the user agent, default headers, proxies or pool sizes via [`reqwest::ClientBuilder`].
+    #[builder(default)]
     reqwest: reqwest::Client,
+    /// The timeout applied to every request.
+    ///
+    /// For one-shot requests this covers the whole exchange, for streaming requests it covers the time until the response starts.
+    #[builder(default, setter(strip_option))]
+    timeout: Option<Duration>,
+    /// The policy for retrying requests that failed because of transient errors.
+    ///
+    /// By default, requests are not retried.
+    #[builder(default, setter(strip_option))]
+    retry_policy: Option<RetryPolicy>,
+    /// The time after which an observe stream without events or heartbeats is considered dead.
+    ///
+    /// The DB sends heartbeats while there are no new events to observe. If neither arrives within this time,
+    /// the stream ends with [`ClientError::HeartbeatTimeout`]. This detects half-open connections that would otherwise hang forever.
+    /// Choose a value that is comfortably larger than the heartbeat interval of the DB. By default, heartbeats are not watched.
+    #[builder(default, setter(strip_option))]
+    heartbeat_timeout: Option<Duration>,
+    /// Whether event candidates are validated against the schemas of their event types before they are written.
+    ///
+    /// The schemas are read from the DB when an unknown event type is written and cached, so invalid data fails early with
+    /// [`ClientError::EventSchemaViolation`] instead of costing a round trip. Use [`Client::load_event_schemas`] to fill the cache up front.
+    /// Schemas registered via other clients are not noticed until [`Client::clear_event_schema_cache`] is called.
+    #[builder(setter(strip_bool))]
+    validate_event_schemas: bool,
+    /// The settings for verifying the signatures of all events that are read or observed.
+    ///
+    /// If set, every event stream of the client is checked as with [`EventStreamExt::verify_signatures_with`],
+    /// so events with an invalid signature arrive as errors. By default, signatures are not verified.
+    #[builder(default, setter(strip_option))]
+    signature_verification: Option<SignatureVerification>,
+    /// The cached event schemas, shared with all clients derived from this one.
+    #[builder(default, setter(skip))]
+    event_schemas: Arc<EventSchemaCache>,
 }
 
 impl Client {
@@ -61,10 +140,27 @@ impl Client {
 
     /// Creates a new client instance based on the base URL and API token
     pub fn new(base_url: Url, api_token: impl Into<String>) -> Self {
-        Client {
-            base_url,
-            api_token: api_token.into(),
-            reqwest: reqwest::Client::new(),
+        Self::builder()
+            .base_url(base_url)
+            .api_token(api_token)
+            .build()
+    }
+
+    /// Creates a copy of the client that uses the given timeout for its requests.
+    ///
+    /// The underlying HTTP client including its connection pool is shared with the original client.
+    /// ```
+    /// # use std::time::Duration;
+    /// # use eventsourcingdb::client::Client;
+    /// # let client = Client::new("http://localhost:8080/".parse().unwrap(), "token");
+    /// let impatient_client = client.with_timeout(Duration::from_millis(500));
+    /// # assert_eq!(impatient_client.get_timeout(), Some(Duration::from_millis(500)));
+    /// ```
+    #[must_use]
+    pub fn with_timeout(&self, timeout: Duration) -> Self {
+        Self {
+            timeout: Some(timeout),
+            ..self.clone()
         }
     }
 
@@ -94,6 +190,141 @@ impl Client {
         &self.api_token
     }
 
+    /// Get the timeout of the client that is applied to every request
+    /// ```
+    /// # use eventsourcingdb::client::Client;
+    /// # let client = Client::new("http://localhost:8080/".parse().unwrap(), "secrettoken");
+    /// let timeout = client.get_timeout();
+    /// # assert_eq!(timeout, None);
+    /// ```
+    #[must_use]
+    pub fn get_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Utility function to limit a request to the configured timeout of the client.
+    ///
+    /// # Errors
+    /// This function will return an error if the future fails or does not complete in time.
+    async fn with_request_timeout<T>(
+        &self,
+        future: impl Future<Output = Result<T, ClientError>>,
+    ) -> Result<T, ClientError> {
+        match self.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, future)
+                .await
+                .map_err(|_| ClientError::RequestTimeout(timeout))?,
+            None => future.await,
+        }
+    }
+
+    /// Get the heartbeat timeout of the client that is applied to observe streams
+    /// ```
+    /// # use eventsourcingdb::client::Client;
+    /// # let client = Client::new("http://localhost:8080/".parse().unwrap(), "secrettoken");
+    /// let heartbeat_timeout = client.get_heartbeat_timeout();
+    /// # assert_eq!(heartbeat_timeout, None);
+    /// ```
+    #[must_use]
+    pub fn get_heartbeat_timeout(&self) -> Option<Duration> {
+        self.heartbeat_timeout
+    }
+
+    /// Utility function to create the heartbeat watchdog for a new stream.
+    fn heartbeat_watchdog(&self) -> HeartbeatWatchdog {
+        HeartbeatWatchdog::new(self.heartbeat_timeout)
+    }
+
+    /// Get the retry policy of the client
+    /// ```
+    /// # use eventsourcingdb::client::Client;
+    /// # let client = Client::new("http://localhost:8080/".parse().unwrap(), "secrettoken");
+    /// let retry_policy = client.get_retry_policy();
+    /// # assert!(retry_policy.is_none());
+    /// ```
+    #[must_use]
+    pub fn get_retry_policy(&self) -> Option<&RetryPolicy> {
+        self.retry_policy.as_ref()
+    }
+
+    /// Get whether the client validates event candidates against their event schemas before writing them
+    /// ```
+    /// # use eventsourcingdb::client::Client;
+    /// # let client = Client::new("http://localhost:8080/".parse().unwrap(), "secrettoken");
+    /// let validate_event_schemas = client.get_validate_event_schemas();
+    /// # assert!(!validate_event_schemas);
+    /// ```
+    #[must_use]
+    pub fn get_validate_event_schemas(&self) -> bool {
+        self.validate_event_schemas
+    }
+
+    /// Get the settings for verifying the signatures of all events that are read or observed
+    /// ```
+    /// # use eventsourcingdb::client::Client;
+    /// # let client = Client::new("http://localhost:8080/".parse().unwrap(), "secrettoken");
+    /// let signature_verification = client.get_signature_verification();
+    /// # assert!(signature_verification.is_none());
+    /// ```
+    #[must_use]
+    pub fn get_signature_verification(&self) -> Option<&SignatureVerification> {
+        self.signature_verification.as_ref()
+    }
+
+    /// Utility function to validate the subject of a read or observe request and the subject of its `from_latest_event` option.
+    fn validate_subjects(
+        subject: &str,
+        from_latest_event_subject: Option<&str>,
+    ) -> Result<(), ClientError> {
+        Subject::validate(subject)?;
+        if let Some(from_latest_event_subject) = from_latest_event_subject {
+            Subject::validate(from_latest_event_subject)?;
+        }
+        Ok(())
+    }
+
+    /// Utility function to verify the signatures of an event stream if the client is configured to do so.
+    fn verify_event_signatures<'a>(
+        &self,
+        events: BoxStream<'a, Result<Event, ClientError>>,
+    ) -> BoxStream<'a, Result<Event, ClientError>> {
+        match &self.signature_verification {
+            Some(signature_verification) => events
+                .verify_signatures_with(signature_verification.clone())
+                .boxed(),
+            None => events,
+        }
+    }
+
+    /// Utility function to verify the signature of a single event if the client is configured to do so.
+    ///
+    /// Returns `None` if the event should be dropped, like [`SignatureVerification::verify`].
+    fn verify_event_signature(&self, event: Event) -> Option<Result<Event, ClientError>> {
+        match &self.signature_verification {
+            Some(signature_verification) => signature_verification.verify(Ok(event)),
+            None => Some(Ok(event)),
+        }
+    }
+
+    /// Utility function to run a request attempt again according to the retry policy of the client.
+    ///
+    /// # Errors
+    /// This function will return the error of the last attempt if all attempts fail or the error is not retryable.
+    async fn with_retries<T, F, Fut>(
+        &self,
+        idempotent: bool,
+        mut attempt: F,
+    ) -> Result<T, ClientError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, ClientError>>,
+    {
+        match &self.retry_policy {
+            Some(retry_policy) => retry_policy.retry(idempotent, attempt).await,
+            None => attempt().await,
+        }
+    }
+
     /// Utility function to request an endpoint of the API.
     ///
     /// This function will return a [`reqwest::RequestBuilder`] which can be used to send the request.
@@ -128,48 +359,87 @@ impl Client {
     /// Utility function to request an endpoint of the API as a oneshot.
     ///
     /// This means, that the response is not streamed, but returned as a single value.
+    /// The timeout of the client covers the whole exchange.
+    /// Failed attempts are retried according to the retry policy of the client.
     ///
     /// # Errors
-    /// This function will return an error if the request fails or if the URL is invalid.
+    /// This function will return an error if the request fails, times out or if the URL is invalid.
     async fn request_oneshot<R: OneShotRequest>(
         &self,
         endpoint: R,
     ) -> Result<R::Response, ClientError> {
-        let response = self.build_request(&endpoint)?.send().await?;
+        let endpoint = &endpoint;
 
-        if response.status().is_success() {
-            Self::validate_server_headers(&response)?;
-            let result = response.json().await?;
-            endpoint.validate_response(&result)?;
-            Ok(result)
-        } else {
-            Err(ClientError::DBApiError(
-                response.status(),
-                response.text().await.unwrap_or_default(),
-            ))
-        }
+        self.with_retries(R::IDEMPOTENT, || async move {
+            let request = self.build_request(endpoint)?;
+
+            self.with_request_timeout(async {
+                let response = request.send().await?;
+
+                if response.status().is_success() {
+                    Self::validate_server_headers(&response)?;
+                    let result = response.json().await?;
+                    endpoint.validate_response(&result)?;
+                    Ok(result)
+                } else {
+                    let status = response.status();
+                    let body = response.text().await.unwrap_or_default();
+                    Err(ClientError::DBApiError(ServerError::new(
+                        Some(status),
+                        &body,
+                    )))
+                }
+            })
+            .await
+        })
+        .await
     }
 
     /// Utility function to request an endpoint of the API as a stream.
     ///
     /// This means, that the response is streamed and returned as a stream of values.
+    /// The timeout of the client only covers the time until the response starts, so long-running streams are not cut off.
+    /// Failed attempts to establish the stream are retried according to the retry policy of the client.
     ///
     /// # Errors
-    /// This function will return an error if the request fails or if the URL is invalid.
+    /// This function will return an error if the request fails, times out or if the URL is invalid.
     async fn request_streaming<R: StreamingRequest>(
         &self,
         endpoint: R,
-    ) -> Result<impl Stream<Item = Result<R::ItemType, ClientError>>, ClientError> {
-        let response = self.build_request(&endpoint)?.send().await?;
-        Self::validate_server_headers(&response)?;
-        if response.status().is_success() {
-            Ok(R::build_stream(response))
-        } else {
-            Err(ClientError::DBApiError(
-                response.status(),
-                response.text().await.unwrap_or_default(),
-            ))
-        }
+    ) -> Result<BoxStream<'static, Result<R::ItemType, ClientError>>, ClientError> {
+        self.request_streaming_watched(endpoint, &self.heartbeat_watchdog())
+            .await
+    }
+
+    /// Utility function to request an endpoint of the API as a stream whose heartbeats are recorded by the given watchdog.
+    ///
+    /// # Errors
+    /// This function will return an error if the request fails, times out or if the URL is invalid.
+    async fn request_streaming_watched<R: StreamingRequest>(
+        &self,
+        endpoint: R,
+        watchdog: &HeartbeatWatchdog,
+    ) -> Result<BoxStream<'static, Result<R::ItemType, ClientError>>, ClientError> {
+        let endpoint = &endpoint;
+
+        self.with_retries(R::IDEMPOTENT, || async move {
+            let request = self.build_request(endpoint)?;
+            let response = self
+                .with_request_timeout(async { Ok(request.send().await?) })
+                .await?;
+            Self::validate_server_headers(&response)?;
+            if response.status().is_success() {
+                Ok(R::build_stream(response, watchdog))
+            } else {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                Err(ClientError::DBApiError(ServerError::new(
+                    Some(status),
+                    &body,
+                )))
+            }
+        })
+        .await
     }
 
     /// Pings the DB instance to check if it is reachable.
@@ -214,16 +484,70 @@ impl Client {
     /// ```
     ///
     /// # Errors
-    /// This function will return an error if the request fails or if the URL is invalid.
+    /// This function will return an error if a subject is malformed, the request fails or if the URL is invalid.
     pub async fn read_events<'a>(
         &self,
-        subject: &'a str,
+        subject: &'a (impl AsRef<str> + ?Sized),
         options: Option<request_options::ReadEventsOptions<'a>>,
     ) -> Result<impl Stream<Item = Result<Event, ClientError>>, ClientError> {
-        let response = self
-            .request_streaming(ReadEventsRequest { subject, options })
-            .await?;
-        Ok(response)
+        let subject = subject.as_ref();
+        Self::validate_subjects(
+            subject,
+            options
+                .as_ref()
+                .and_then(|o| o.from_latest_event.as_ref())
+                .map(|from_latest_event| from_latest_event.subject),
+        )?;
+        let response = self.read_events_unverified(subject, options).await?;
+        Ok(self.verify_event_signatures(response))
+    }
+
+    /// Utility function to read events without verifying their signatures, even if the client is configured to do so.
+    ///
+    /// This is used by [`crate::audit::audit`], which reports invalid signatures instead of failing on them.
+    pub(crate) async fn read_events_unverified<'a>(
+        &self,
+        subject: &'a str,
+        options: Option<request_options::ReadEventsOptions<'a>>,
+    ) -> Result<BoxStream<'static, Result<Event, ClientError>>, ClientError> {
+        self.request_streaming(ReadEventsRequest { subject, options })
+            .await
+    }
+
+    /// Reads events from the DB instance and deserializes their data into the given type.
+    ///
+    /// ```
+    /// use eventsourcingdb::event::EventCandidate;
+    /// use futures::StreamExt;
+    /// # use serde_json::json;
+    /// #[derive(Debug, serde::Deserialize)]
+    /// struct Counter {
+    ///     value: u32,
+    /// }
+    /// # tokio_test::block_on(async {
+    /// # let container = eventsourcingdb::container::Container::start_preview().await.unwrap();
+    /// let db_url = "http://localhost:3000/";
+    /// let api_token = "secrettoken";
+    /// # let db_url = container.get_base_url().await.unwrap();
+    /// # let api_token = container.get_api_token();
+    /// let client = eventsourcingdb::client::Client::new(db_url, api_token);
+    /// let mut event_stream = client.read_events_typed::<Counter>("/", None).await.expect("Failed to read events");
+    /// while let Some(event) = event_stream.next().await {
+    ///     println!("Found Counter {:?}", event.expect("Error while reading events").data());
+    /// }
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    /// This function will return an error if a subject is malformed, the request fails or if the URL is invalid.
+    /// Events whose data does not match the given type are returned as errors in the stream.
+    pub async fn read_events_typed<'a, T: DeserializeOwned>(
+        &self,
+        subject: &'a (impl AsRef<str> + ?Sized),
+        options: Option<request_options::ReadEventsOptions<'a>>,
+    ) -> Result<impl Stream<Item = Result<TypedEvent<T>, ClientError>>, ClientError> {
+        let events = self.read_events(subject, options).await?;
+        Ok(events.map(|event| Ok(TypedEvent::try_from(event?)?)))
     }
 
     /// Reads a specific event type from the DB instance.
@@ -303,16 +627,200 @@ impl Client {
     /// ```
     ///
     /// # Errors
-    /// This function will return an error if the request fails or if the URL is invalid.
+    /// This function will return an error if a subject is malformed, the request fails or if the URL is invalid.
     pub async fn observe_events<'a>(
         &self,
-        subject: &'a str,
+        subject: &'a (impl AsRef<str> + ?Sized),
         options: Option<request_options::ObserveEventsOptions<'a>>,
-    ) -> Result<impl Stream<Item = Result<Event, ClientError>>, ClientError> {
+    ) -> Result<ObservedEvents<'static>, ClientError> {
+        let subject = subject.as_ref();
+        Self::validate_subjects(
+            subject,
+            options
+                .as_ref()
+                .and_then(|o| o.from_latest_event.as_ref())
+                .map(|from_latest_event| from_latest_event.subject),
+        )?;
+        let watchdog = self.heartbeat_watchdog();
         let response = self
-            .request_streaming(ObserveEventsRequest { subject, options })
+            .request_streaming_watched(ObserveEventsRequest { subject, options }, &watchdog)
             .await?;
-        Ok(response)
+        Ok(ObservedEvents::new(
+            self.verify_event_signatures(response),
+            watchdog,
+        ))
+    }
+
+    /// Observe events from the DB instance and deserialize their data into the given type.
+    ///
+    /// ```
+    /// use eventsourcingdb::event::EventCandidate;
+    /// use futures::StreamExt;
+    /// # use serde_json::json;
+    /// #[derive(Debug, serde::Deserialize)]
+    /// struct Counter {
+    ///     value: u32,
+    /// }
+    /// # tokio_test::block_on(async {
+    /// # let container = eventsourcingdb::container::Container::start_preview().await.unwrap();
+    /// let db_url = "http://localhost:3000/";
+    /// let api_token = "secrettoken";
+    /// # let db_url = container.get_base_url().await.unwrap();
+    /// # let api_token = container.get_api_token();
+    /// let client = eventsourcingdb::client::Client::new(db_url, api_token);
+    /// # client.write_events(
+    /// #   vec![
+    /// #     EventCandidate::builder()
+    /// #        .source("https://www.eventsourcingdb.io".to_string())
+    /// #        .data(json!({"value": 1}))
+    /// #        .subject("/test".to_string())
+    /// #        .ty("io.eventsourcingdb.test".to_string())
+    /// #        .build()
+    /// #   ],
+    /// #   vec![]
+    /// # ).await.expect("Failed to write events");
+    /// let mut event_stream = client.observe_events_typed::<Counter>("/test", None).await.expect("Failed to observe events");
+    /// match event_stream.next().await {
+    ///     Some(Ok(event)) => println!("Found Counter {:?}", event.data()),
+    ///     Some(Err(e)) => eprintln!("Error while reading event: {:?}", e),
+    ///     None => println!("No more events."),
+    /// }
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    /// This function will return an error if a subject is malformed, the request fails or if the URL is invalid.
+    /// Events whose data does not match the given type are returned as errors in the stream.
+    pub async fn observe_events_typed<'a, T: DeserializeOwned>(
+        &self,
+        subject: &'a (impl AsRef<str> + ?Sized),
+        options: Option<request_options::ObserveEventsOptions<'a>>,
+    ) -> Result<impl Stream<Item = Result<TypedEvent<T>, ClientError>>, ClientError> {
+        let events = self.observe_events(subject, options).await?;
+        Ok(events.map(|event| Ok(TypedEvent::try_from(event?)?)))
+    }
+
+    /// Observe events from the DB instance and reconnect automatically whenever the connection is lost.
+    ///
+    /// The stream remembers the ID of the last delivered event and continues after it when reconnecting,
+    /// so you get one endless stream of events without gaps or duplicates.
+    /// Reconnecting uses the backoff of the given retry policy, also after the DB closed the connection.
+    /// Only failed connection attempts are counted, so losing a connection again and again never ends the stream, e.g. when a proxy
+    /// closes idle connections. The stream ends with an error if the DB could not be reached [`RetryPolicy::max_attempts`] times in a row
+    /// or if an error occurs that is not retryable.
+    /// Events that fail the signature verification of the client are yielded as errors, and the stream continues after them.
+    ///
+    /// ```
+    /// use eventsourcingdb::{client::RetryPolicy, event::EventCandidate};
+    /// use futures::StreamExt;
+    /// # use serde_json::json;
+    /// # tokio_test::block_on(async {
+    /// # let container = eventsourcingdb::container::Container::start_preview().await.unwrap();
+    /// let db_url = "http://localhost:3000/";
+    /// let api_token = "secrettoken";
+    /// # let db_url = container.get_base_url().await.unwrap();
+    /// # let api_token = container.get_api_token();
+    /// let client = eventsourcingdb::client::Client::new(db_url, api_token);
+    /// # client.write_events(
+    /// #   vec![
+    /// #     EventCandidate::builder()
+    /// #        .source("https://www.eventsourcingdb.io".to_string())
+    /// #        .data(json!({"value": 1}))
+    /// #        .subject("/test".to_string())
+    /// #        .ty("io.eventsourcingdb.test".to_string())
+    /// #        .build()
+    /// #   ],
+    /// #   vec![]
+    /// # ).await.expect("Failed to write events");
+    /// let retry_policy = RetryPolicy::builder().max_attempts(10).build();
+    /// let mut event_stream = client
+    ///     .observe_events_resilient("/test", None, retry_policy)
+    ///     .await
+    ///     .expect("Failed to observe events");
+    /// match event_stream.next().await {
+    ///     Some(Ok(event)) => println!("Found Event {:?}", event),
+    ///     Some(Err(e)) => eprintln!("Error while reading event: {:?}", e),
+    ///     None => println!("No more events."),
+    /// }
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    /// This function will return an error if the subject is malformed or the initial connection could not be established.
+    pub async fn observe_events_resilient<'a>(
+        &'a self,
+        subject: &'a (impl AsRef<str> + ?Sized),
+        options: Option<request_options::ObserveEventsOptions<'a>>,
+        retry_policy: RetryPolicy,
+    ) -> Result<ObservedEvents<'a>, ClientError> {
+        let subject = subject.as_ref();
+        Self::validate_subjects(
+            subject,
+            options
+                .as_ref()
+                .and_then(|o| o.from_latest_event.as_ref())
+                .map(|from_latest_event| from_latest_event.subject),
+        )?;
+        let mut observation = ResilientObservation::new(self, subject, options, retry_policy);
+        observation.connect().await?;
+        Ok(observation.into_stream())
+    }
+
+    /// Subscribe to the events of a subject, starting with the events that have already been written.
+    ///
+    /// The stream first delivers the past events, then a [`SubscriptionItem::CaughtUp`] marker, and then the new events
+    /// as they are written. Observing continues after the last past event, so there are neither gaps nor duplicates.
+    /// Events that fail the signature verification of the client are yielded as errors, and the stream continues after them.
+    ///
+    /// ```
+    /// use eventsourcingdb::{client::SubscriptionItem, event::EventCandidate, request_options::SubscribeOptions};
+    /// use futures::StreamExt;
+    /// # use serde_json::json;
+    /// # tokio_test::block_on(async {
+    /// # let container = eventsourcingdb::container::Container::start_preview().await.unwrap();
+    /// let db_url = "http://localhost:3000/";
+    /// let api_token = "secrettoken";
+    /// # let db_url = container.get_base_url().await.unwrap();
+    /// # let api_token = container.get_api_token();
+    /// let client = eventsourcingdb::client::Client::new(db_url, api_token);
+    /// # client.write_events(
+    /// #   vec![
+    /// #     EventCandidate::builder()
+    /// #        .source("https://www.eventsourcingdb.io".to_string())
+    /// #        .data(json!({"value": 1}))
+    /// #        .subject("/test".to_string())
+    /// #        .ty("io.eventsourcingdb.test".to_string())
+    /// #        .build()
+    /// #   ],
+    /// #   vec![]
+    /// # ).await.expect("Failed to write events");
+    /// let options = SubscribeOptions {
+    ///     recursive: true,
+    ///     ..Default::default()
+    /// };
+    /// let mut item_stream = client.subscribe("/", Some(options)).await.expect("Failed to subscribe");
+    /// while let Some(item) = item_stream.next().await {
+    ///     match item.expect("Error while reading events") {
+    ///         SubscriptionItem::Event(event) => println!("Found Event {:?}", event),
+    ///         SubscriptionItem::CaughtUp => break,
+    ///     }
+    /// }
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    /// This function will return an error if a subject is malformed, the request fails or if the URL is invalid.
+    pub async fn subscribe<'a>(
+        &'a self,
+        subject: &'a (impl AsRef<str> + ?Sized),
+        options: Option<request_options::SubscribeOptions<'a>>,
+    ) -> Result<impl Stream<Item = Result<SubscriptionItem, ClientError>> + 'a, ClientError> {
+        let subject = subject.as_ref();
+        Subject::validate(subject)?;
+        let options = options.unwrap_or_default();
+        let subscription =
+            Subscription::start(self, subject, options.lower_bound, options.recursive).await?;
+        Ok(subscription.into_stream())
     }
 
     /// Verifies the API token by sending a request to the DB instance.
@@ -373,10 +881,166 @@ impl Client {
         event_type: &str,
         schema: &serde_json::Value,
     ) -> Result<ManagementEvent, ClientError> {
-        self.request_oneshot(RegisterEventSchemaRequest::try_new(event_type, schema)?)
+        let response = self
+            .request_oneshot(RegisterEventSchemaRequest::try_new(event_type, schema)?)
+            .await?;
+        if self.validate_event_schemas {
+            self.event_schemas.insert(event_type, Some(schema))?;
+        }
+        Ok(response)
+    }
+
+    /// Registers the JSON schema generated from a Rust type as the event schema for an event type.
+    ///
+    /// The schema is generated using [schemars], see [`event_schema_for`].
+    /// Use [`Client::compare_event_schema_for`] to check the schema against the DB without registering it.
+    /// ```
+    /// # tokio_test::block_on(async {
+    /// # let container = eventsourcingdb::container::Container::start_preview().await.unwrap();
+    /// let db_url = "http://localhost:3000/";
+    /// let api_token = "secrettoken";
+    /// # let db_url = container.get_base_url().await.unwrap();
+    /// # let api_token = container.get_api_token();
+    /// let client = eventsourcingdb::client::Client::new(db_url, api_token);
+    /// #[derive(schemars::JsonSchema)]
+    /// struct BookAcquired {
+    ///     title: String,
+    ///     author: String,
+    /// }
+    /// client
+    ///     .register_event_schema_for::<BookAcquired>("io.eventsourcingdb.library.book-acquired")
+    ///     .await
+    ///     .expect("Failed to register event schema");
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    /// This function will return an error if the request fails or if the generated schema is invalid.
+    #[cfg(feature = "schemars")]
+    pub async fn register_event_schema_for<T: schemars::JsonSchema>(
+        &self,
+        event_type: &str,
+    ) -> Result<ManagementEvent, ClientError> {
+        self.register_event_schema(event_type, &event_schema_for::<T>())
             .await
     }
 
+    /// Compares the JSON schema generated from a Rust type with the schema registered for an event type.
+    ///
+    /// This is a dry run of [`Client::register_event_schema_for`], which does not register anything.
+    /// ```
+    /// use eventsourcingdb::client::EventSchemaComparison;
+    /// # tokio_test::block_on(async {
+    /// # let container = eventsourcingdb::container::Container::start_preview().await.unwrap();
+    /// let db_url = "http://localhost:3000/";
+    /// let api_token = "secrettoken";
+    /// # let db_url = container.get_base_url().await.unwrap();
+    /// # let api_token = container.get_api_token();
+    /// let client = eventsourcingdb::client::Client::new(db_url, api_token);
+    /// #[derive(schemars::JsonSchema)]
+    /// struct BookAcquired {
+    ///     title: String,
+    /// }
+    /// let comparison = client
+    ///     .compare_event_schema_for::<BookAcquired>("io.eventsourcingdb.library.book-acquired")
+    ///     .await
+    ///     .expect("Failed to compare event schema");
+    /// assert!(matches!(comparison, EventSchemaComparison::NotRegistered { .. }));
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    /// This function will return an error if the request fails.
+    #[cfg(feature = "schemars")]
+    pub async fn compare_event_schema_for<T: schemars::JsonSchema>(
+        &self,
+        event_type: &str,
+    ) -> Result<EventSchemaComparison, ClientError> {
+        let registered = match self.read_event_type(event_type).await {
+            Ok(event_type) => event_type.schema,
+            Err(err) if err.is_not_found() => None,
+            Err(err) => return Err(err),
+        };
+        Ok(EventSchemaComparison::new(
+            registered,
+            event_schema_for::<T>(),
+        ))
+    }
+
+    /// Reads the schemas of all event types from the DB into the cache used for validating event candidates.
+    ///
+    /// This only has an effect if the client validates event schemas, see [`Client::builder`].
+    /// Otherwise, the schemas are read lazily the first time an event type is written.
+    /// ```
+    /// # tokio_test::block_on(async {
+    /// # let container = eventsourcingdb::container::Container::start_preview().await.unwrap();
+    /// let db_url = "http://localhost:3000/";
+    /// let api_token = "secrettoken";
+    /// # let db_url = container.get_base_url().await.unwrap();
+    /// # let api_token = container.get_api_token();
+    /// let client = eventsourcingdb::client::Client::builder()
+    ///     .base_url(db_url)
+    ///     .api_token(api_token)
+    ///     .validate_event_schemas()
+    ///     .build();
+    /// client.load_event_schemas().await.expect("Failed to load event schemas");
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    /// This function will return an error if the request fails or if a schema is invalid.
+    pub async fn load_event_schemas(&self) -> Result<(), ClientError> {
+        if !self.validate_event_schemas {
+            return Ok(());
+        }
+        self.cache_event_schemas().await
+    }
+
+    /// Removes all cached event schemas, so they are read from the DB again on the next write.
+    pub fn clear_event_schema_cache(&self) {
+        self.event_schemas.clear();
+    }
+
+    /// Utility function to read the schemas of all event types from the DB into the cache.
+    ///
+    /// # Errors
+    /// This function will return an error if the request fails or if a schema is invalid.
+    async fn cache_event_schemas(&self) -> Result<(), ClientError> {
+        let mut event_types = self.list_event_types().await?;
+        while let Some(event_type) = event_types.next().await {
+            let event_type = event_type?;
+            self.event_schemas
+                .insert(&event_type.name, event_type.schema.as_ref())?;
+        }
+        Ok(())
+    }
+
+    /// Utility function to validate event candidates against the schemas of their event types.
+    ///
+    /// If an event type is not cached yet, the schemas of all event types are read from the DB.
+    /// Event types the DB does not know are cached without a schema.
+    ///
+    /// # Errors
+    /// This function will return an error if the schemas can't be read or an event candidate violates its schema.
+    async fn validate_event_candidates(
+        &self,
+        events: &[EventCandidate],
+    ) -> Result<(), ClientError> {
+        if events
+            .iter()
+            .any(|event| !self.event_schemas.contains(&event.ty))
+        {
+            self.cache_event_schemas().await?;
+            for event in events {
+                if !self.event_schemas.contains(&event.ty) {
+                    self.event_schemas.insert(&event.ty, None)?;
+                }
+            }
+        }
+        self.event_schemas.validate(events)?;
+        Ok(())
+    }
+
     /// List all subjects in the DB instance.
     ///
     /// To get all subjects in the DB, just pass `None` as the `base_subject`.
@@ -418,11 +1082,14 @@ impl Client {
     /// ```
     ///
     /// # Errors
-    /// This function will return an error if the request fails or if the URL is invalid.
+    /// This function will return an error if a subject is malformed, the request fails or if the URL is invalid.
     pub async fn list_subjects(
         &self,
         base_subject: Option<&str>,
     ) -> Result<impl Stream<Item = Result<String, ClientError>>, ClientError> {
+        if let Some(base_subject) = base_subject {
+            Subject::validate(base_subject)?;
+        }
         let response = self
             .request_streaming(ListSubjectsRequest {
                 base_subject: base_subject.unwrap_or("/"),
@@ -431,6 +1098,60 @@ impl Client {
         Ok(response)
     }
 
+    /// Build a tree of the subjects below the base subject.
+    ///
+    /// Subjects returned by the DB that are not valid [`Subject`]s are left out of the tree.
+    ///
+    /// ```
+    /// use eventsourcingdb::event::EventCandidate;
+    /// # use serde_json::json;
+    /// # tokio_test::block_on(async {
+    /// # let container = eventsourcingdb::container::Container::start_preview().await.unwrap();
+    /// let db_url = "http://localhost:3000/";
+    /// let api_token = "secrettoken";
+    /// # let db_url = container.get_base_url().await.unwrap();
+    /// # let api_token = container.get_api_token();
+    /// let client = eventsourcingdb::client::Client::new(db_url, api_token);
+    /// # client.write_events(
+    /// #   vec![
+    /// #     EventCandidate::builder()
+    /// #        .source("https://www.eventsourcingdb.io".to_string())
+    /// #        .data(json!({"reader": "Jane"}))
+    /// #        .subject("/books/42/loans".to_string())
+    /// #        .ty("io.eventsourcingdb.library.book-borrowed".to_string())
+    /// #        .build()
+    /// #   ],
+    /// #   vec![]
+    /// # ).await.expect("Failed to write events");
+    /// let tree = client.subject_tree(Some("/books")).await.expect("Failed to list subjects");
+    /// for book in tree.descendants(Some(1)) {
+    ///     println!("Found {} with {} nested subjects", book.subject(), book.descendant_count());
+    /// }
+    /// for loans in tree.matching("/books/*/loans").expect("Invalid pattern") {
+    ///     println!("Found loans {}", loans.subject());
+    /// }
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    /// This function will return an error if the base subject is malformed, the request fails or if the URL is invalid.
+    pub async fn subject_tree(
+        &self,
+        base_subject: Option<&str>,
+    ) -> Result<SubjectTree, ClientError> {
+        let base = Subject::new(base_subject.unwrap_or("/"))?;
+        let mut subjects = Vec::new();
+        {
+            let mut response = self.list_subjects(Some(&base)).await?;
+            while let Some(subject) = response.next().await {
+                if let Ok(subject) = Subject::new(subject?) {
+                    subjects.push(subject);
+                }
+            }
+        }
+        Ok(SubjectTree::new(base, subjects))
+    }
+
     /// List all event types in the DB instance.
     ///
     /// ```
@@ -485,12 +1206,22 @@ impl Client {
     /// ```
     ///
     /// # Errors
-    /// This function will return an error if the request fails or if the URL is invalid.
+    /// This function will return an error if a subject is malformed, the request fails or if the URL is invalid.
     pub async fn write_events(
         &self,
         events: Vec<EventCandidate>,
         preconditions: Vec<Precondition>,
     ) -> Result<Vec<Event>, ClientError> {
+        for subject in events
+            .iter()
+            .map(|event| event.subject.as_str())
+            .chain(preconditions.iter().filter_map(Precondition::subject))
+        {
+            Subject::validate(subject)?;
+        }
+        if self.validate_event_schemas {
+            self.validate_event_candidates(&events).await?;
+        }
         self.request_oneshot(WriteEventsRequest {
             events,
             preconditions,
@@ -530,4 +1261,47 @@ impl Client {
             .await?;
         Ok(response)
     }
+
+    /// Run an eventql query against the DB and deserialize every row into the given type.
+    ///
+    /// ```
+    /// use futures::StreamExt;
+    /// # tokio_test::block_on(async {
+    /// # let container = eventsourcingdb::container::Container::start_preview().await.unwrap();
+    /// let db_url = "http://localhost:3000/";
+    /// let api_token = "secrettoken";
+    /// # let db_url = container.get_base_url().await.unwrap();
+    /// # let api_token = container.get_api_token();
+    /// let client = eventsourcingdb::client::Client::new(db_url, api_token);
+    /// #[derive(Debug, serde::Deserialize)]
+    /// struct BookTitle {
+    ///     title: String,
+    /// }
+    /// let query = r#"FROM e IN events WHERE e.type == "io.eventsourcingdb.library.book-acquired" PROJECT INTO { title: e.data.title }"#;
+    /// let mut row_stream = client.run_eventql_query_as::<BookTitle>(query).await.expect("Failed to run query");
+    /// while let Some(row) = row_stream.next().await {
+    ///     println!("Found book {:?}", row.expect("Error while reading row"));
+    /// }
+    /// # })
+    /// ```
+    ///
+    /// # Errors
+    /// This function will return an error if the request fails or if the URL is invalid.
+    /// Rows that don't match the type are returned as [`crate::error::RowDeserializationError`] within the stream,
+    /// the following rows are still delivered.
+    pub async fn run_eventql_query_as<T: DeserializeOwned>(
+        &self,
+        query: &str,
+    ) -> Result<impl Stream<Item = Result<T, ClientError>>, ClientError> {
+        let rows = self.run_eventql_query(query).await?;
+        // Errors of the stream itself are not rows, so only received rows are counted.
+        let mut next_index = 0;
+        Ok(rows.map(move |row| {
+            let row = row?;
+            let index = next_index;
+            next_index += 1;
+            T::deserialize(&row)
+                .map_err(|source| RowDeserializationError { index, row, source }.into())
+        }))
+    }
 }
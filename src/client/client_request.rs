@@ -23,15 +23,20 @@ use serde_json::value::RawValue;
 pub use verify_api_token::VerifyApiTokenRequest;
 pub use write_events::WriteEventsRequest;
 
-use crate::error::ClientError;
+use crate::error::{ClientError, ServerError};
+use chrono::{DateTime, Utc};
 use futures::{
-    Stream,
-    stream::{StreamExt, TryStreamExt},
+    future,
+    stream::{self, BoxStream, StreamExt, TryStreamExt},
 };
 use futures_util::io;
 use reqwest::Method;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::{
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio_stream::wrappers::LinesStream;
 use tokio_util::io::StreamReader;
@@ -40,6 +45,8 @@ use tokio_util::io::StreamReader;
 pub trait ClientRequest {
     const URL_PATH: &'static str;
     const METHOD: Method;
+    /// Whether sending the request more than once has the same effect as sending it once
+    const IDEMPOTENT: bool = true;
 
     /// Returns the URL path for the request
     fn url_path(&self) -> &'static str {
@@ -76,49 +83,122 @@ struct StreamLineItem {
     payload: Box<RawValue>,
 }
 
+/// Watches the heartbeats the database sends on a long-running stream
+///
+/// Clones share the time of the last heartbeat, so every stream needs its own watchdog.
+#[derive(Debug, Clone)]
+pub struct HeartbeatWatchdog {
+    /// The time after which a stream without events or heartbeats is considered dead
+    timeout: Option<Duration>,
+    /// The time the last heartbeat was received
+    last_heartbeat: Arc<Mutex<Option<DateTime<Utc>>>>,
+}
+
+impl HeartbeatWatchdog {
+    /// Creates a watchdog for a new stream that has not received any heartbeats yet
+    pub fn new(timeout: Option<Duration>) -> Self {
+        Self {
+            timeout,
+            last_heartbeat: Arc::default(),
+        }
+    }
+
+    /// Returns the time the last heartbeat was received
+    pub fn last_heartbeat(&self) -> Option<DateTime<Utc>> {
+        *self
+            .last_heartbeat
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Records that a heartbeat was received just now
+    fn record_heartbeat(&self) {
+        *self
+            .last_heartbeat
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(Utc::now());
+    }
+
+    /// Ends the stream with an error if no line is received within the timeout
+    fn watch(
+        &self,
+        lines: BoxStream<'static, Result<String, ClientError>>,
+    ) -> BoxStream<'static, Result<String, ClientError>> {
+        let Some(timeout) = self.timeout else {
+            return lines;
+        };
+        stream::unfold(Some(lines), move |lines| async move {
+            let mut lines = lines?;
+            match tokio::time::timeout(timeout, lines.next()).await {
+                Ok(Some(line)) => Some((line, Some(lines))),
+                Ok(None) => None,
+                Err(_) => Some((Err(ClientError::HeartbeatTimeout(timeout)), None)),
+            }
+        })
+        .boxed()
+    }
+}
+
 /// Represents a request to the database that expects a stream of responses
 pub trait StreamingRequest: ClientRequest {
-    type ItemType: DeserializeOwned;
+    type ItemType: DeserializeOwned + Send + 'static;
     const ITEM_TYPE_NAME: &'static str;
+    /// Whether the database keeps the stream open and sends heartbeats while no items are available
+    const SENDS_HEARTBEATS: bool = false;
 
     fn build_stream(
         response: reqwest::Response,
-    ) -> impl Stream<Item = Result<Self::ItemType, ClientError>> {
-        Box::pin(
+        watchdog: &HeartbeatWatchdog,
+    ) -> BoxStream<'static, Result<Self::ItemType, ClientError>> {
+        let lines = if Self::SENDS_HEARTBEATS {
+            watchdog.watch(Self::lines_stream(response))
+        } else {
             Self::lines_stream(response)
-                .map(|line| Ok(serde_json::from_str::<StreamLineItem>(line?.as_str())?))
-                .filter_map(|o| async {
-                    match o {
-                        // A line was successfully parsed.
-                        Ok(StreamLineItem { payload, ty }) => match ty.as_str() {
-                            // This is the expected type, so we try to parse it.
-                            ty if ty == Self::ITEM_TYPE_NAME => {
-                                Some(serde_json::from_str(payload.get()).map_err(ClientError::from))
-                            }
-                            // Forward Errors from the DB as DBErrors.
-                            "error" => Some(Err(ClientError::DBError(payload.get().to_string()))),
-                            // Ignore heartbeat messages.
-                            "heartbeat" => None,
-                            other => Some(Err(ClientError::InvalidResponseType(format!(
-                                "Expected type {}, but got {}",
-                                Self::ITEM_TYPE_NAME,
-                                other
-                            )))),
-                        },
-                        // An error occured while parsing the line, which we forward as an error.
-                        Err(e) => Some(Err(e)),
-                    }
-                }),
-        )
+        };
+        let watchdog = watchdog.clone();
+        lines
+            .map(|line| Ok(serde_json::from_str::<StreamLineItem>(line?.as_str())?))
+            .filter_map(move |o| {
+                let item = match o {
+                    // A line was successfully parsed.
+                    Ok(StreamLineItem { payload, ty }) => match ty.as_str() {
+                        // This is the expected type, so we try to parse it.
+                        ty if ty == Self::ITEM_TYPE_NAME => {
+                            Some(serde_json::from_str(payload.get()).map_err(ClientError::from))
+                        }
+                        // Forward Errors from the DB as DBErrors.
+                        "error" => Some(Err(ClientError::DBError(ServerError::new(
+                            None,
+                            payload.get(),
+                        )))),
+                        // Only record heartbeat messages.
+                        "heartbeat" => {
+                            watchdog.record_heartbeat();
+                            None
+                        }
+                        other => Some(Err(ClientError::InvalidResponseType(format!(
+                            "Expected type {}, but got {}",
+                            Self::ITEM_TYPE_NAME,
+                            other
+                        )))),
+                    },
+                    // An error occured while parsing the line, which we forward as an error.
+                    Err(e) => Some(Err(e)),
+                };
+                future::ready(item)
+            })
+            .boxed()
     }
 
     fn lines_stream(
         response: reqwest::Response,
-    ) -> impl Stream<Item = Result<String, ClientError>> {
+    ) -> BoxStream<'static, Result<String, ClientError>> {
         let bytes = response
             .bytes_stream()
             .map_err(|err| io::Error::other(format!("Failed to read response stream: {err}")));
         let stream_reader = StreamReader::new(bytes);
-        LinesStream::new(BufReader::new(stream_reader).lines()).map_err(ClientError::from)
+        LinesStream::new(BufReader::new(stream_reader).lines())
+            .map_err(ClientError::from)
+            .boxed()
     }
 }
@@ -26,6 +26,7 @@ impl<'a> RegisterEventSchemaRequest<'a> {
 impl ClientRequest for RegisterEventSchemaRequest<'_> {
     const URL_PATH: &'static str = "/api/v1/register-event-schema";
     const METHOD: Method = Method::POST;
+    const IDEMPOTENT: bool = false;
 
     fn body(&self) -> Option<Result<impl Serialize, ClientError>> {
         Some(Ok(self))
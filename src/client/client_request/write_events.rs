@@ -16,6 +16,7 @@ pub struct WriteEventsRequest {
 impl ClientRequest for WriteEventsRequest {
     const URL_PATH: &'static str = "/api/v1/write-events";
     const METHOD: Method = Method::POST;
+    const IDEMPOTENT: bool = false;
 
     fn body(&self) -> Option<Result<impl Serialize, ClientError>> {
         Some(Ok(self))
@@ -0,0 +1,74 @@
+use schemars::JsonSchema;
+use serde_json::Value;
+
+/// Result of comparing the JSON schema generated for a Rust type with the schema registered in the DB.
+///
+/// This is returned by [`crate::client::Client::compare_event_schema_for`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventSchemaComparison {
+    /// No schema is registered for the event type yet, so registering the generated schema would succeed.
+    NotRegistered {
+        /// The schema generated for the Rust type
+        generated: Value,
+    },
+    /// The registered schema matches the generated schema.
+    Unchanged {
+        /// The schema generated for the Rust type, which is the same as the registered one
+        schema: Value,
+    },
+    /// The registered schema differs from the generated schema.
+    ///
+    /// The DB does not allow changing a registered schema, so the Rust type has drifted from the schema in the DB.
+    Changed {
+        /// The schema registered in the DB
+        registered: Value,
+        /// The schema generated for the Rust type
+        generated: Value,
+    },
+}
+
+impl EventSchemaComparison {
+    pub(crate) fn new(registered: Option<Value>, generated: Value) -> Self {
+        match registered {
+            None => Self::NotRegistered { generated },
+            Some(registered) if registered == generated => Self::Unchanged { schema: generated },
+            Some(registered) => Self::Changed {
+                registered,
+                generated,
+            },
+        }
+    }
+
+    /// Whether the registered schema matches the generated schema.
+    #[must_use]
+    pub fn is_unchanged(&self) -> bool {
+        matches!(self, Self::Unchanged { .. })
+    }
+
+    /// Get the schema generated for the Rust type.
+    #[must_use]
+    pub fn generated(&self) -> &Value {
+        match self {
+            Self::NotRegistered { generated }
+            | Self::Unchanged { schema: generated }
+            | Self::Changed { generated, .. } => generated,
+        }
+    }
+}
+
+/// Generate the JSON schema for the data of an event from a Rust type.
+///
+/// This is the schema [`crate::client::Client::register_event_schema_for`] registers.
+/// ```
+/// use eventsourcingdb::client::event_schema_for;
+/// #[derive(schemars::JsonSchema)]
+/// struct BookAcquired {
+///     title: String,
+/// }
+/// let schema = event_schema_for::<BookAcquired>();
+/// assert_eq!(schema["required"], serde_json::json!(["title"]));
+/// ```
+#[must_use]
+pub fn event_schema_for<T: JsonSchema>() -> Value {
+    schemars::schema_for!(T).to_value()
+}
@@ -0,0 +1,95 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use jsonschema::Validator;
+use serde_json::Value;
+
+use crate::{
+    error::{ClientError, EventSchemaViolation},
+    event::EventCandidate,
+};
+
+/// Cache of the validators for the event schemas registered in the DB.
+///
+/// Event types without a registered schema are cached as `None`, so they are not looked up again.
+#[derive(Default)]
+pub(crate) struct EventSchemaCache {
+    validators: Mutex<HashMap<String, Option<Arc<Validator>>>>,
+}
+
+impl EventSchemaCache {
+    /// Check whether the cache knows about an event type.
+    pub fn contains(&self, event_type: &str) -> bool {
+        self.validators
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains_key(event_type)
+    }
+
+    /// Cache the schema of an event type, replacing an existing entry.
+    ///
+    /// # Errors
+    /// This function will return an error if the schema is not a valid JSON schema.
+    pub fn insert(&self, event_type: &str, schema: Option<&Value>) -> Result<(), ClientError> {
+        let validator = match schema {
+            Some(schema) => Some(Arc::new(
+                jsonschema::validator_for(schema).map_err(|_e| ClientError::JsonSchemaError)?,
+            )),
+            None => None,
+        };
+        let _ = self
+            .validators
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(event_type.to_string(), validator);
+        Ok(())
+    }
+
+    /// Remove all cached schemas.
+    pub fn clear(&self) {
+        self.validators
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+
+    /// Validate the data of event candidates against the cached schemas of their event types.
+    ///
+    /// Event types that are not cached or have no schema are not validated.
+    ///
+    /// # Errors
+    /// This function will return an error describing the first violation.
+    pub fn validate(&self, candidates: &[EventCandidate]) -> Result<(), EventSchemaViolation> {
+        let validators = self
+            .validators
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        for (index, candidate) in candidates.iter().enumerate() {
+            let Some(Some(validator)) = validators.get(&candidate.ty) else {
+                continue;
+            };
+            if let Err(error) = validator.validate(&candidate.data) {
+                return Err(EventSchemaViolation {
+                    index,
+                    event_type: candidate.ty.clone(),
+                    pointer: error.instance_path.to_string(),
+                    message: error.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for EventSchemaCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let validators = self
+            .validators
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        f.debug_set().entries(validators.keys()).finish()
+    }
+}
@@ -0,0 +1,51 @@
+use std::{
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt, stream::BoxStream};
+
+use crate::{client::client_request::HeartbeatWatchdog, error::ClientError, event::Event};
+
+/// A stream of observed events, see [`crate::client::Client::observe_events`].
+///
+/// Besides the events, the stream keeps track of the heartbeats the DB sends on this connection while there are no new events.
+pub struct ObservedEvents<'a> {
+    events: BoxStream<'a, Result<Event, ClientError>>,
+    watchdog: HeartbeatWatchdog,
+}
+
+impl<'a> ObservedEvents<'a> {
+    pub(crate) fn new(
+        events: BoxStream<'a, Result<Event, ClientError>>,
+        watchdog: HeartbeatWatchdog,
+    ) -> Self {
+        Self { events, watchdog }
+    }
+
+    /// Get the time the last heartbeat was received on this stream.
+    ///
+    /// This is `None` until the first heartbeat arrives. For streams that reconnect, heartbeats of all connections count.
+    #[must_use]
+    pub fn last_heartbeat(&self) -> Option<DateTime<Utc>> {
+        self.watchdog.last_heartbeat()
+    }
+}
+
+impl Stream for ObservedEvents<'_> {
+    type Item = Result<Event, ClientError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.events.poll_next_unpin(cx)
+    }
+}
+
+impl fmt::Debug for ObservedEvents<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObservedEvents")
+            .field("last_heartbeat", &self.last_heartbeat())
+            .finish_non_exhaustive()
+    }
+}
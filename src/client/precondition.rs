@@ -1,7 +1,14 @@
+use std::collections::HashSet;
+
 use serde::Serialize;
 
+use crate::{
+    error::PreconditionError,
+    event::{Event, EventCandidate},
+};
+
 /// Enum for different preconditions that can be used when writing events
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 #[serde(tag = "type", content = "payload")]
 pub enum Precondition {
     /// Check if the subject with the given path has no other events
@@ -32,3 +39,227 @@ pub enum Precondition {
         query: String,
     },
 }
+
+impl Precondition {
+    /// Get the subject the precondition refers to, if any.
+    #[must_use]
+    pub fn subject(&self) -> Option<&str> {
+        match self {
+            Self::IsSubjectPristine { subject }
+            | Self::IsSubjectPopulated { subject }
+            | Self::IsSubjectOnEventId { subject, .. } => Some(subject),
+            Self::IsEventQLQueryTrue { .. } => None,
+        }
+    }
+}
+
+/// Builder for the preconditions of a write that spans multiple subjects.
+///
+/// ```
+/// use eventsourcingdb::{EventCandidate, Preconditions};
+/// use futures::TryStreamExt;
+/// # use serde_json::json;
+/// # tokio_test::block_on(async {
+/// # let container = eventsourcingdb::container::Container::start_preview().await.unwrap();
+/// let db_url = "http://localhost:3000/";
+/// let api_token = "secrettoken";
+/// # let db_url = container.get_base_url().await.unwrap();
+/// # let api_token = container.get_api_token();
+/// let client = eventsourcingdb::client::Client::new(db_url, api_token);
+/// # let _ = client.write_events(vec![EventCandidate::builder()
+/// #     .source("https://www.eventsourcingdb.io".to_string())
+/// #     .data(json!({"title": "2001 - A Space Odyssey"}))
+/// #     .subject("/books/42".to_string())
+/// #     .ty("io.eventsourcingdb.library.book-acquired".to_string())
+/// #     .build()], vec![]).await.unwrap();
+/// let book_events: Vec<_> = client
+///     .read_events("/books/42", None)
+///     .await
+///     .expect("Failed to read events")
+///     .try_collect()
+///     .await
+///     .expect("Failed to read events");
+/// let candidates = vec![
+///     EventCandidate::builder()
+///         .source("https://www.eventsourcingdb.io".to_string())
+///         .data(json!({"reader": "Jane"}))
+///         .subject("/books/42".to_string())
+///         .ty("io.eventsourcingdb.library.book-borrowed".to_string())
+///         .build(),
+///     EventCandidate::builder()
+///         .source("https://www.eventsourcingdb.io".to_string())
+///         .data(json!({"book": "42"}))
+///         .subject("/loans/1".to_string())
+///         .ty("io.eventsourcingdb.library.loan-started".to_string())
+///         .build(),
+/// ];
+/// let preconditions = Preconditions::new()
+///     .with_subjects_on_events(&book_events)
+///     .with_pristine_new_subjects(&candidates)
+///     .build_for(&candidates)
+///     .expect("Invalid preconditions");
+/// client.write_events(candidates, preconditions).await.expect("Failed to write events");
+/// # })
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Preconditions {
+    preconditions: Vec<Precondition>,
+}
+
+impl Preconditions {
+    /// Create an empty set of preconditions.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a precondition.
+    #[must_use]
+    pub fn with_precondition(mut self, precondition: Precondition) -> Self {
+        self.preconditions.push(precondition);
+        self
+    }
+
+    /// Require the subject of the event to still be on this event.
+    ///
+    /// If there is already such a precondition for the subject, it is replaced, so the last loaded event wins.
+    #[must_use]
+    pub fn with_subject_on_event(mut self, event: &Event) -> Self {
+        self.preconditions.retain(|precondition| {
+            !matches!(precondition, Precondition::IsSubjectOnEventId { subject, .. } if subject == event.subject())
+        });
+        self.with_precondition(Precondition::IsSubjectOnEventId {
+            subject: event.subject().to_string(),
+            event_id: event.id().to_string(),
+        })
+    }
+
+    /// Require the subjects of the events to still be on the last of the given events for each subject.
+    #[must_use]
+    pub fn with_subjects_on_events<'a>(self, events: impl IntoIterator<Item = &'a Event>) -> Self {
+        events.into_iter().fold(self, |preconditions, event| {
+            preconditions.with_subject_on_event(event)
+        })
+    }
+
+    /// Require the subject to have no events.
+    #[must_use]
+    pub fn with_subject_pristine(self, subject: impl Into<String>) -> Self {
+        self.with_precondition(Precondition::IsSubjectPristine {
+            subject: subject.into(),
+        })
+    }
+
+    /// Require the subject to have events.
+    #[must_use]
+    pub fn with_subject_populated(self, subject: impl Into<String>) -> Self {
+        self.with_precondition(Precondition::IsSubjectPopulated {
+            subject: subject.into(),
+        })
+    }
+
+    /// Require an EventQL query to return true.
+    #[must_use]
+    pub fn with_eventql_query(self, query: impl Into<String>) -> Self {
+        self.with_precondition(Precondition::IsEventQLQueryTrue {
+            query: query.into(),
+        })
+    }
+
+    /// Require every subject of the candidates that has no precondition yet to have no events.
+    ///
+    /// Call this after adding the preconditions for the subjects that are already known.
+    #[must_use]
+    pub fn with_pristine_new_subjects(self, candidates: &[EventCandidate]) -> Self {
+        let mut covered: HashSet<String> = self
+            .preconditions
+            .iter()
+            .filter_map(Precondition::subject)
+            .map(ToString::to_string)
+            .collect();
+        let new_subjects: Vec<String> = candidates
+            .iter()
+            .filter(|candidate| covered.insert(candidate.subject.clone()))
+            .map(|candidate| candidate.subject.clone())
+            .collect();
+        new_subjects
+            .into_iter()
+            .fold(self, Self::with_subject_pristine)
+    }
+
+    /// Check that the preconditions fit the candidates.
+    ///
+    /// # Errors
+    /// Returns an error if a precondition refers to a subject none of the candidates is written to,
+    /// or if a subject has preconditions that can never be met together.
+    pub fn validate(&self, candidates: &[EventCandidate]) -> Result<(), PreconditionError> {
+        let subjects: HashSet<&str> = candidates
+            .iter()
+            .map(|candidate| candidate.subject.as_str())
+            .collect();
+        for (index, precondition) in self.preconditions.iter().enumerate() {
+            let Some(subject) = precondition.subject() else {
+                continue;
+            };
+            if !subjects.contains(subject) {
+                return Err(PreconditionError::UnknownSubject {
+                    subject: subject.to_string(),
+                });
+            }
+            let conflicts = self.preconditions[..index]
+                .iter()
+                .filter(|other| other.subject() == Some(subject))
+                .any(|other| conflict(precondition, other));
+            if conflicts {
+                return Err(PreconditionError::Conflicting {
+                    subject: subject.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the preconditions.
+    #[must_use]
+    pub fn build(self) -> Vec<Precondition> {
+        self.preconditions
+    }
+
+    /// Get the preconditions after checking that they fit the candidates, see [`Preconditions::validate`].
+    ///
+    /// # Errors
+    /// Returns an error if the preconditions don't fit the candidates.
+    pub fn build_for(
+        self,
+        candidates: &[EventCandidate],
+    ) -> Result<Vec<Precondition>, PreconditionError> {
+        self.validate(candidates)?;
+        Ok(self.build())
+    }
+}
+
+impl From<Preconditions> for Vec<Precondition> {
+    fn from(preconditions: Preconditions) -> Self {
+        preconditions.build()
+    }
+}
+
+/// Utility function to check whether two preconditions on the same subject can never be met together.
+fn conflict(precondition: &Precondition, other: &Precondition) -> bool {
+    match (precondition, other) {
+        (Precondition::IsSubjectPristine { .. }, Precondition::IsSubjectPristine { .. })
+        | (Precondition::IsSubjectPopulated { .. }, Precondition::IsSubjectPopulated { .. }) => {
+            false
+        }
+        (
+            Precondition::IsSubjectOnEventId { event_id, .. },
+            Precondition::IsSubjectOnEventId {
+                event_id: other_event_id,
+                ..
+            },
+        ) => event_id != other_event_id,
+        (Precondition::IsSubjectPristine { .. }, _)
+        | (_, Precondition::IsSubjectPristine { .. }) => true,
+        _ => false,
+    }
+}
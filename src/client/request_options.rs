@@ -37,6 +37,15 @@ pub struct ObserveEventsOptions<'a> {
     pub recursive: bool,
 }
 
+/// Options for subscribing to events, see [`crate::client::Client::subscribe`]
+#[derive(Debug, Default, Clone)]
+pub struct SubscribeOptions<'a> {
+    /// Lower bound of events to read
+    pub lower_bound: Option<Bound<'a>>,
+    /// Include recursive subject's events
+    pub recursive: bool,
+}
+
 /// Ordering of the responses of requests
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "kebab-case")]
@@ -0,0 +1,130 @@
+use futures::{
+    StreamExt,
+    stream::{self, BoxStream},
+};
+
+use crate::{
+    client::{
+        Client, ObservedEvents, RetryPolicy,
+        client_request::{HeartbeatWatchdog, ObserveEventsRequest},
+        request_options::{Bound, BoundType, ObserveEventsOptions},
+    },
+    error::ClientError,
+    event::Event,
+};
+
+/// State of an observation that reconnects whenever the connection to the DB is lost.
+///
+/// After the first event has been delivered, every reconnect continues with an exclusive lower bound
+/// on the last delivered event, so consumers get neither gaps nor duplicates.
+pub(crate) struct ResilientObservation<'a> {
+    client: &'a Client,
+    subject: &'a str,
+    options: ObserveEventsOptions<'a>,
+    retry_policy: RetryPolicy,
+    last_event_id: Option<String>,
+    events: Option<BoxStream<'static, Result<Event, ClientError>>>,
+    /// The watchdog shared by all connections, so the last heartbeat survives reconnects
+    watchdog: HeartbeatWatchdog,
+    finished: bool,
+}
+
+impl<'a> ResilientObservation<'a> {
+    pub(crate) fn new(
+        client: &'a Client,
+        subject: &'a str,
+        options: Option<ObserveEventsOptions<'a>>,
+        retry_policy: RetryPolicy,
+    ) -> Self {
+        Self {
+            client,
+            subject,
+            options: options.unwrap_or_default(),
+            retry_policy,
+            last_event_id: None,
+            events: None,
+            watchdog: client.heartbeat_watchdog(),
+            finished: false,
+        }
+    }
+
+    /// (Re)connect to the DB, retrying according to the retry policy.
+    ///
+    /// # Errors
+    /// This function will return the last error if no connection could be established.
+    pub(crate) async fn connect(&mut self) -> Result<(), ClientError> {
+        let events = self
+            .retry_policy
+            .retry(true, || {
+                let mut options = self.options.clone();
+                if let Some(last_event_id) = &self.last_event_id {
+                    options.from_latest_event = None;
+                    options.lower_bound = Some(Bound {
+                        bound_type: BoundType::Exclusive,
+                        id: last_event_id,
+                    });
+                }
+                self.client.request_streaming_watched(
+                    ObserveEventsRequest {
+                        subject: self.subject,
+                        options: Some(options),
+                    },
+                    &self.watchdog,
+                )
+            })
+            .await?;
+        // Signatures are verified per event in `into_stream`, so rejected events still move the lower bound.
+        self.events = Some(events);
+        Ok(())
+    }
+
+    /// Handle a lost connection by waiting for the initial backoff of the retry policy before reconnecting.
+    ///
+    /// A lost connection is not counted as a failed attempt, only the attempts of the following [`Self::connect`] are.
+    async fn disconnected(&mut self) {
+        self.events = None;
+        tokio::time::sleep(self.retry_policy.backoff(0)).await;
+    }
+
+    /// Turn the observation into an endless stream of events.
+    ///
+    /// The stream only ends with an error, either one that is not retryable or the last one after reconnecting failed
+    /// [`RetryPolicy::max_attempts`] times in a row. Losing an established connection never ends the stream.
+    /// Events that fail the signature verification are yielded as errors without ending the stream.
+    pub(crate) fn into_stream(self) -> ObservedEvents<'a> {
+        let watchdog = self.watchdog.clone();
+        let events = stream::unfold(self, |mut observation| async move {
+            loop {
+                if observation.finished {
+                    return None;
+                }
+                let Some(events) = observation.events.as_mut() else {
+                    if let Err(error) = observation.connect().await {
+                        observation.finished = true;
+                        return Some((Err(error), observation));
+                    }
+                    continue;
+                };
+                match events.next().await {
+                    Some(Ok(event)) => {
+                        observation.last_event_id = Some(event.id().to_string());
+                        if let Some(event) = observation.client.verify_event_signature(event) {
+                            return Some((event, observation));
+                        }
+                    }
+                    Some(Err(error)) if observation.retry_policy.is_retryable(&error, true) => {
+                        observation.disconnected().await;
+                    }
+                    Some(Err(error)) => {
+                        observation.finished = true;
+                        return Some((Err(error), observation));
+                    }
+                    // The DB closed the connection, so we reconnect.
+                    None => observation.disconnected().await,
+                }
+            }
+        })
+        .boxed();
+        ObservedEvents::new(events, watchdog)
+    }
+}
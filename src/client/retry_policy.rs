@@ -0,0 +1,137 @@
+use std::{
+    future::Future,
+    hash::{BuildHasher, RandomState},
+    time::Duration,
+};
+
+use reqwest::StatusCode;
+use typed_builder::TypedBuilder;
+
+use crate::error::ClientError;
+
+/// Policy for retrying requests that failed because of transient errors.
+///
+/// Idempotent requests like [`crate::client::Client::ping`], [`crate::client::Client::read_event_type`],
+/// the `list_*` requests or [`crate::client::Client::run_eventql_query`] are retried on connection errors,
+/// timeouts and retryable status codes.
+/// Requests that change data, like [`crate::client::Client::write_events`], are only retried if the error
+/// proves that the request never reached the server.
+///
+/// For streaming requests, only establishing the stream is retried.
+/// ```
+/// # use std::time::Duration;
+/// # use eventsourcingdb::client::{Client, RetryPolicy};
+/// let retry_policy = RetryPolicy::builder()
+///     .max_attempts(5)
+///     .initial_backoff(Duration::from_millis(200))
+///     .build();
+/// let client = Client::builder()
+///     .base_url("http://localhost:3000/".parse().unwrap())
+///     .api_token("secrettoken")
+///     .retry_policy(retry_policy)
+///     .build();
+/// ```
+#[derive(Debug, Clone, TypedBuilder)]
+pub struct RetryPolicy {
+    /// The maximum number of attempts, including the first one.
+    #[builder(default = 3)]
+    pub max_attempts: u32,
+    /// The delay before the first retry.
+    #[builder(default = Duration::from_millis(100))]
+    pub initial_backoff: Duration,
+    /// The upper limit for the delay between two attempts.
+    #[builder(default = Duration::from_secs(5))]
+    pub max_backoff: Duration,
+    /// The factor the delay grows with after every retry.
+    #[builder(default = 2)]
+    pub multiplier: u32,
+    /// Whether to randomize the delay to avoid many clients retrying at the same time.
+    #[builder(default = true)]
+    pub jitter: bool,
+    /// Decides whether a response with the given status code is worth retrying.
+    #[builder(default = RetryPolicy::is_transient_status)]
+    pub retryable_status: fn(StatusCode) -> bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl RetryPolicy {
+    /// The default predicate for retryable status codes.
+    ///
+    /// This treats `502 Bad Gateway`, `503 Service Unavailable` and `504 Gateway Timeout` as transient.
+    #[must_use]
+    pub fn is_transient_status(status: StatusCode) -> bool {
+        matches!(
+            status,
+            StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT
+        )
+    }
+
+    /// Get the delay to wait before the given retry.
+    ///
+    /// The first retry has the number `0`. The delay grows exponentially and is capped at [`RetryPolicy::max_backoff`].
+    /// If [`RetryPolicy::jitter`] is enabled, a random delay between half and the full backoff is returned.
+    #[must_use]
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = self.multiplier.saturating_pow(retry);
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff);
+
+        if self.jitter {
+            // The std hasher is randomly seeded, which is good enough for spreading retries.
+            let random = RandomState::new().hash_one(retry);
+            let half = backoff / 2;
+            let jitter_nanos = u64::try_from(half.as_nanos()).unwrap_or(u64::MAX);
+            half + Duration::from_nanos(random % jitter_nanos.saturating_add(1))
+        } else {
+            backoff
+        }
+    }
+
+    /// Check whether a request that failed with the given error should be retried.
+    pub(crate) fn is_retryable(&self, error: &ClientError, idempotent: bool) -> bool {
+        match error {
+            ClientError::ReqwestError(error) if error.is_connect() => true,
+            ClientError::ReqwestError(error) => {
+                idempotent && (error.is_timeout() || error.is_request())
+            }
+            ClientError::RequestTimeout(_)
+            | ClientError::HeartbeatTimeout(_)
+            | ClientError::IoError(_) => idempotent,
+            ClientError::DBApiError(error) => {
+                idempotent && error.status().is_some_and(self.retryable_status)
+            }
+            _ => false,
+        }
+    }
+
+    /// Run a request attempt again until it succeeds, fails with an error that is not retryable or the attempts are used up.
+    pub(crate) async fn retry<T, F, Fut>(
+        &self,
+        idempotent: bool,
+        mut attempt: F,
+    ) -> Result<T, ClientError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, ClientError>>,
+    {
+        let mut retry = 0;
+        loop {
+            match attempt().await {
+                Err(error)
+                    if retry + 1 < self.max_attempts && self.is_retryable(&error, idempotent) =>
+                {
+                    tokio::time::sleep(self.backoff(retry)).await;
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
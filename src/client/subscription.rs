@@ -0,0 +1,150 @@
+use futures::{
+    Stream, StreamExt,
+    stream::{self, BoxStream},
+};
+
+use crate::{
+    client::{
+        Client,
+        client_request::{ObserveEventsRequest, ReadEventsRequest},
+        request_options::{Bound, BoundType, ObserveEventsOptions, ReadEventsOptions},
+    },
+    error::ClientError,
+    event::Event,
+};
+
+/// An item of a subscription, see [`Client::subscribe`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubscriptionItem {
+    /// An event of the subscribed subject
+    Event(Box<Event>),
+    /// All events written before the subscription started have been delivered, every following event is new
+    CaughtUp,
+}
+
+/// The phase a subscription is in.
+enum Phase {
+    /// Reading the events that were written before the subscription started
+    Reading(BoxStream<'static, Result<Event, ClientError>>),
+    /// Observing the events that are written from now on
+    Observing(BoxStream<'static, Result<Event, ClientError>>),
+    /// The subscription ended because of an error
+    Finished,
+}
+
+/// State of a subscription that reads the past events of a subject and then switches to observing it.
+///
+/// The observation starts with an exclusive lower bound on the last read event, so there are neither gaps nor duplicates.
+pub(crate) struct Subscription<'a> {
+    client: &'a Client,
+    subject: &'a str,
+    lower_bound: Option<Bound<'a>>,
+    recursive: bool,
+    last_event_id: Option<String>,
+    phase: Phase,
+}
+
+impl<'a> Subscription<'a> {
+    /// Start reading the past events of a subject.
+    ///
+    /// # Errors
+    /// This function will return an error if the events can't be read.
+    pub(crate) async fn start(
+        client: &'a Client,
+        subject: &'a str,
+        lower_bound: Option<Bound<'a>>,
+        recursive: bool,
+    ) -> Result<Self, ClientError> {
+        let events = client
+            .request_streaming(ReadEventsRequest {
+                subject,
+                options: Some(ReadEventsOptions {
+                    lower_bound: lower_bound.clone(),
+                    recursive,
+                    ..Default::default()
+                }),
+            })
+            .await?;
+        Ok(Self {
+            client,
+            subject,
+            lower_bound,
+            recursive,
+            last_event_id: None,
+            phase: Phase::Reading(events),
+        })
+    }
+
+    /// Utility function to get the lower bound for observing the events written after the last read event.
+    fn observe_lower_bound(&self) -> Option<Bound<'_>> {
+        match &self.last_event_id {
+            Some(last_event_id) => Some(Bound {
+                bound_type: BoundType::Exclusive,
+                id: last_event_id,
+            }),
+            None => self.lower_bound.clone(),
+        }
+    }
+
+    /// Utility function to switch from reading to observing the events written after the last read event.
+    async fn observe(&mut self) -> Result<SubscriptionItem, ClientError> {
+        let events = self
+            .client
+            .request_streaming(ObserveEventsRequest {
+                subject: self.subject,
+                options: Some(ObserveEventsOptions {
+                    lower_bound: self.observe_lower_bound(),
+                    recursive: self.recursive,
+                    ..Default::default()
+                }),
+            })
+            .await;
+        match events {
+            Ok(events) => {
+                self.phase = Phase::Observing(events);
+                Ok(SubscriptionItem::CaughtUp)
+            }
+            Err(error) => {
+                self.phase = Phase::Finished;
+                Err(error)
+            }
+        }
+    }
+
+    /// Turn the subscription into a stream of events with a [`SubscriptionItem::CaughtUp`] marker between past and new events.
+    pub(crate) fn into_stream(
+        self,
+    ) -> impl Stream<Item = Result<SubscriptionItem, ClientError>> + 'a {
+        stream::unfold(self, |mut subscription| async move {
+            loop {
+                let event = match &mut subscription.phase {
+                    Phase::Reading(events) => match events.next().await {
+                        Some(Ok(event)) => {
+                            subscription.last_event_id = Some(event.id().to_string());
+                            event
+                        }
+                        Some(Err(error)) => {
+                            subscription.phase = Phase::Finished;
+                            return Some((Err(error), subscription));
+                        }
+                        None => {
+                            let item = subscription.observe().await;
+                            return Some((item, subscription));
+                        }
+                    },
+                    Phase::Observing(events) => match events.next().await? {
+                        Ok(event) => event,
+                        Err(error) => return Some((Err(error), subscription)),
+                    },
+                    Phase::Finished => return None,
+                };
+                // Signatures are verified per event, so a rejected event doesn't end the subscription.
+                if let Some(event) = subscription.client.verify_event_signature(event) {
+                    let item = event.map(|event| SubscriptionItem::Event(Box::new(event)));
+                    return Some((item, subscription));
+                }
+            }
+        })
+        .boxed()
+    }
+}
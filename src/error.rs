@@ -1,6 +1,9 @@
 //! This module contains all error types of the SDK.
 
+use std::time::Duration;
+
 use reqwest::{self, StatusCode};
+use serde_json::Value;
 use thiserror::Error;
 
 /// Error type for the client
@@ -24,22 +27,43 @@ pub enum ClientError {
     /// There was a problem making a request to the DB
     #[error("The request failed with error: {0}")]
     ReqwestError(#[from] reqwest::Error),
+    /// The request did not complete within the configured timeout
+    #[error("The request timed out after {0:?}")]
+    RequestTimeout(Duration),
+    /// The DB sent neither items nor heartbeats within the configured heartbeat timeout
+    #[error("No heartbeat was received within {0:?}")]
+    HeartbeatTimeout(Duration),
     /// There was a problem parsing the URL
     #[error("The URL is invalid: {0}")]
     URLParseError(#[from] url::ParseError),
     /// There was a problem with the JSON serialization
     #[error("The JSON serialization failed: {0}")]
     SerdeJsonError(#[from] serde_json::Error),
-    /// The DB returned an error in the response
+    /// The DB returned an error within a streamed response
     #[error("The DB returned an error in the response: {0}")]
-    DBError(String),
-    /// The DB returned an error
+    DBError(ServerError),
+    /// There was a problem with an event received from the DB
+    #[error("The event is invalid: {0}")]
+    EventError(#[from] EventError),
+    /// The DB rejected the request with an error status
     #[error("The DB returned an error: {0}")]
-    DBApiError(StatusCode, String),
+    DBApiError(ServerError),
     // check if this can hold a validation error in the future
     /// The passed jsonschema is invalid
     #[error("The passed jsonschema is invalid")]
     JsonSchemaError,
+    /// The hash chain of the events is broken
+    #[error("The integrity of the events is violated: {0}")]
+    IntegrityError(#[from] IntegrityError),
+    /// The data of an event candidate does not match the schema of its event type
+    #[error("The event candidate does not match its schema: {0}")]
+    EventSchemaViolation(#[from] EventSchemaViolation),
+    /// The provided subject is malformed
+    #[error("The provided subject is invalid: {0}")]
+    InvalidSubject(#[from] SubjectError),
+    /// A row of an EventQL query does not match the requested type
+    #[error("The row of the query is invalid: {0}")]
+    RowDeserializationError(#[from] RowDeserializationError),
     /// There was a problem with the `cloudevents` message
     #[cfg(feature = "cloudevents")]
     #[error("The CloudEvents message is invalid: {0}")]
@@ -52,6 +76,358 @@ pub enum ClientError {
     InvalidServerHeader,
 }
 
+impl ClientError {
+    /// Get the error returned by the DB, if the request was rejected by the DB.
+    #[must_use]
+    pub fn server_error(&self) -> Option<&ServerError> {
+        match self {
+            Self::DBApiError(error) | Self::DBError(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Whether the DB rejected the request because a precondition failed.
+    #[must_use]
+    pub fn is_precondition_failed(&self) -> bool {
+        self.server_error()
+            .is_some_and(ServerError::is_precondition_failed)
+    }
+
+    /// Whether the DB rejected the API token.
+    #[must_use]
+    pub fn is_unauthorized(&self) -> bool {
+        matches!(self, Self::APITokenInvalid)
+            || self
+                .server_error()
+                .is_some_and(ServerError::is_unauthorized)
+    }
+
+    /// Whether an event did not match the schema of its event type.
+    ///
+    /// This covers violations detected by the DB as well as violations detected by the client, see [`EventSchemaViolation`].
+    #[must_use]
+    pub fn is_schema_violation(&self) -> bool {
+        matches!(self, Self::EventSchemaViolation(_))
+            || self
+                .server_error()
+                .is_some_and(ServerError::is_schema_violation)
+    }
+
+    /// Whether the DB could not find the requested resource.
+    #[must_use]
+    pub fn is_not_found(&self) -> bool {
+        self.server_error().is_some_and(ServerError::is_not_found)
+    }
+}
+
+/// The reason the DB rejected a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ServerErrorKind {
+    /// A precondition of a write was not met
+    PreconditionFailed,
+    /// The API token is missing or invalid
+    Unauthorized,
+    /// The API token is not allowed to perform the request
+    Forbidden,
+    /// The requested resource does not exist
+    NotFound,
+    /// An event does not match the schema of its event type
+    SchemaViolation,
+    /// The request is malformed
+    BadRequest,
+    /// The DB failed to handle the request or is unavailable
+    Internal,
+    /// Any other error
+    Other,
+}
+
+/// An error returned by the DB.
+///
+/// The DB reports errors either with an error status and a message in the body, or as an error item within a streamed response.
+/// The kind of the error is derived from the status and the message, while the message and any further details are kept as sent by the DB.
+/// ```
+/// use eventsourcingdb::error::{ServerError, ServerErrorKind};
+/// use reqwest::StatusCode;
+/// let error = ServerError::new(Some(StatusCode::CONFLICT), "precondition failed");
+/// assert_eq!(error.kind(), ServerErrorKind::PreconditionFailed);
+/// assert!(error.is_precondition_failed());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{message} ({kind:?})")]
+pub struct ServerError {
+    status: Option<StatusCode>,
+    kind: ServerErrorKind,
+    message: String,
+    details: Option<Value>,
+}
+
+impl ServerError {
+    /// Create an error from the status and the body the DB responded with.
+    ///
+    /// If the body is a JSON object, its `error` or `message` field is used as the message and the whole object is kept as details.
+    /// Otherwise the body is used as the message.
+    #[must_use]
+    pub fn new(status: Option<StatusCode>, body: &str) -> Self {
+        let details = serde_json::from_str::<Value>(body)
+            .ok()
+            .filter(|value| !value.is_string());
+        let message = match &details {
+            Some(Value::Object(object)) => ["error", "message", "detail"]
+                .iter()
+                .find_map(|key| object.get(*key).and_then(Value::as_str))
+                .map(str::to_string),
+            _ => None,
+        }
+        .or_else(|| serde_json::from_str::<String>(body).ok())
+        .unwrap_or_else(|| body.trim().to_string());
+        let message = if message.is_empty() {
+            status
+                .and_then(|status| status.canonical_reason())
+                .unwrap_or_default()
+                .to_string()
+        } else {
+            message
+        };
+        Self {
+            status,
+            kind: ServerErrorKind::classify(status, &message),
+            message,
+            details,
+        }
+    }
+
+    /// Get the HTTP status the DB responded with.
+    ///
+    /// This is `None` for errors sent within a streamed response.
+    #[must_use]
+    pub fn status(&self) -> Option<StatusCode> {
+        self.status
+    }
+    /// Get the kind of the error.
+    #[must_use]
+    pub fn kind(&self) -> ServerErrorKind {
+        self.kind
+    }
+    /// Get the message of the error.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+    /// Get the details of the error, if the DB sent them as JSON.
+    #[must_use]
+    pub fn details(&self) -> Option<&Value> {
+        self.details.as_ref()
+    }
+    /// Whether a precondition of a write was not met.
+    #[must_use]
+    pub fn is_precondition_failed(&self) -> bool {
+        self.kind == ServerErrorKind::PreconditionFailed
+    }
+    /// Whether the API token is missing or invalid.
+    #[must_use]
+    pub fn is_unauthorized(&self) -> bool {
+        self.kind == ServerErrorKind::Unauthorized
+    }
+    /// Whether an event does not match the schema of its event type.
+    #[must_use]
+    pub fn is_schema_violation(&self) -> bool {
+        self.kind == ServerErrorKind::SchemaViolation
+    }
+    /// Whether the requested resource does not exist.
+    #[must_use]
+    pub fn is_not_found(&self) -> bool {
+        self.kind == ServerErrorKind::NotFound
+    }
+}
+
+impl ServerErrorKind {
+    /// Derive the kind of an error from the status and the message the DB responded with.
+    fn classify(status: Option<StatusCode>, message: &str) -> Self {
+        let message = message.to_lowercase();
+        match status {
+            Some(StatusCode::CONFLICT | StatusCode::PRECONDITION_FAILED) => {
+                Self::PreconditionFailed
+            }
+            Some(StatusCode::UNAUTHORIZED) => Self::Unauthorized,
+            Some(StatusCode::FORBIDDEN) => Self::Forbidden,
+            Some(StatusCode::NOT_FOUND) => Self::NotFound,
+            Some(StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY)
+                if message.contains("schema") =>
+            {
+                Self::SchemaViolation
+            }
+            Some(StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY) => Self::BadRequest,
+            Some(status) if status.is_server_error() => Self::Internal,
+            None if message.contains("precondition") => Self::PreconditionFailed,
+            None if message.contains("schema") => Self::SchemaViolation,
+            None if message.contains("not found") => Self::NotFound,
+            Some(_) | None => Self::Other,
+        }
+    }
+}
+
+/// Describes where the hash chain of a stream of events is broken, see [`crate::event::EventStreamExt::verify_chain`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum IntegrityError {
+    /// The hash of an event does not match its content
+    #[error("the hash of event {event_id} is invalid, expected {expected} but computed {actual}")]
+    InvalidHash {
+        /// The ID of the offending event
+        event_id: String,
+        /// The hash sent by the DB
+        expected: String,
+        /// The hash computed from the content of the event
+        actual: String,
+    },
+    /// The predecessor hash of an event does not match the hash of the event before it
+    #[error(
+        "event {event_id} does not follow event {predecessor_id}, expected predecessor hash {expected} but found {actual}"
+    )]
+    BrokenChain {
+        /// The ID of the offending event
+        event_id: String,
+        /// The ID of the event before the offending event
+        predecessor_id: String,
+        /// The hash of the event before the offending event
+        expected: String,
+        /// The predecessor hash of the offending event
+        actual: String,
+    },
+}
+
+/// Describes an event candidate whose data does not match the schema registered for its event type.
+///
+/// This is detected on the client side before writing events, see [`crate::client::Client::builder`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("candidate {index} of type {event_type} is invalid at '{pointer}': {message}")]
+pub struct EventSchemaViolation {
+    /// The index of the failing event candidate in the written batch
+    pub index: usize,
+    /// The event type of the failing event candidate
+    pub event_type: String,
+    /// The JSON pointer to the invalid value within the data of the event candidate
+    pub pointer: String,
+    /// A description of the violation
+    pub message: String,
+}
+
+/// Describes a row of an EventQL query that could not be deserialized into the requested type.
+///
+/// See [`crate::client::Client::run_eventql_query_as`].
+#[derive(Debug, Error)]
+#[error("row {index} could not be deserialized: {source}")]
+pub struct RowDeserializationError {
+    /// The index of the failing row among the rows received so far, not counting errors of the stream itself
+    pub index: usize,
+    /// The raw JSON of the failing row
+    pub row: Value,
+    /// The error returned by the deserializer
+    #[source]
+    pub source: serde_json::Error,
+}
+
+/// Describes a malformed subject, see [`crate::Subject`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("'{subject}' is not a valid subject because {reason}")]
+pub struct SubjectError {
+    /// The malformed subject
+    pub subject: String,
+    /// Why the subject is malformed
+    pub reason: String,
+}
+
+/// Error type for values that can't be used in an EventQL query, see [`crate::eventql::EventQl`].
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum EventQlError {
+    /// The name is not an identifier or it is a reserved word
+    #[error("'{name}' is not a valid EventQL identifier")]
+    InvalidIdentifier {
+        /// The invalid name
+        name: String,
+    },
+    /// The number is NaN or infinite, which EventQL has no literal for
+    #[error("{value} is not a finite number and can't be used in EventQL")]
+    NonFiniteNumber {
+        /// The invalid number
+        value: f64,
+    },
+}
+
+/// Describes a syntax error in an EventQL query, see [`crate::eventql::check`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("line {line}, column {column}: {message}")]
+pub struct EventQlSyntaxError {
+    /// The line of the error, starting at 1
+    pub line: usize,
+    /// The column of the error within its line, starting at 1
+    pub column: usize,
+    /// A description of the error
+    pub message: String,
+}
+
+impl EventQlSyntaxError {
+    pub(crate) fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+/// Error type for preconditions that don't fit the written events, see [`crate::Preconditions::validate`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PreconditionError {
+    /// A precondition refers to a subject none of the events is written to
+    #[error("The precondition refers to {subject}, but no event is written to it")]
+    UnknownSubject {
+        /// The subject of the precondition
+        subject: String,
+    },
+    /// The preconditions of a subject can never be met together
+    #[error("The preconditions of {subject} contradict each other")]
+    Conflicting {
+        /// The subject of the preconditions
+        subject: String,
+    },
+}
+
+/// Error type for executing a command, see [`crate::aggregate::Repository::execute_command`].
+#[derive(Debug, Error)]
+pub enum CommandError<E> {
+    /// The command was rejected by the decision
+    #[error("The command was rejected: {0:?}")]
+    Rejected(E),
+    /// The subject kept changing while the command was executed
+    #[error("The command conflicted with concurrent writes to {subject} {attempts} times")]
+    Conflict {
+        /// The subject the command was executed on
+        subject: String,
+        /// The number of attempts made to execute the command
+        attempts: u32,
+    },
+    /// There was a problem reading or writing the events
+    #[error("The command failed with error: {0}")]
+    ClientError(#[from] ClientError),
+}
+
+/// Error type for running a projection, see [`crate::projection::ProjectionRunner::run`].
+#[derive(Debug, Error)]
+pub enum ProjectionError<E> {
+    /// The projection could not process an event
+    #[error("The projection failed to process event {event_id}: {error:?}")]
+    Projection {
+        /// The ID of the event that could not be processed
+        event_id: String,
+        /// The error returned by the projection
+        error: E,
+    },
+    /// There was a problem observing the events or storing the checkpoint
+    #[error("The projection failed with error: {0}")]
+    ClientError(#[from] ClientError),
+}
+
 /// Error type for the [`crate::container`] feature.
 #[cfg(feature = "testcontainer")]
 #[derive(Debug, Error)]
@@ -87,6 +463,9 @@ pub enum EventError {
     /// Serde error
     #[error("Serde error: {0}")]
     SerdeError(#[from] serde_json::Error),
+    /// The event has a type that the Rust type it should be converted into does not represent
+    #[error("The event type {0} is not expected")]
+    UnexpectedEventType(String),
     /// Signature missing for event
     #[error("Signature is missing for the event")]
     MissingSignature,
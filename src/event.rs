@@ -1,15 +1,26 @@
 //! This module holds all event types that are send between the client and the database.
 
+mod event_sourcing_db_event;
+mod event_stream_ext;
 mod event_types;
+mod signature_verification;
 mod trace_info;
 
 // Reexport relevant types to flatten the module graph for consumers and
 // keep private encapsulation of implementation details.
+pub use event_sourcing_db_event::EventSourcingDbEvent;
+pub(crate) use event_stream_ext::ChainVerifier;
+pub use event_stream_ext::EventStreamExt;
 pub use event_types::event::Event;
 pub use event_types::event_candidate::EventCandidate;
 pub use event_types::management_event::ManagementEvent;
+pub use event_types::typed_event::TypedEvent;
+pub use signature_verification::{SignatureVerification, UnsignedEventPolicy};
 pub use trace_info::TraceInfo;
 
+#[cfg(feature = "derive")]
+pub use eventsourcingdb_derive::EventSourcingDbEvent;
+
 #[cfg(feature = "cloudevents")]
 pub use crate::error::EventError;
 
@@ -0,0 +1,106 @@
+//! This module holds the trait for Rust types that represent events.
+
+use serde_json::Value;
+
+use crate::{
+    error::EventError,
+    event::{Event, EventCandidate},
+};
+
+/// Represents a Rust type that can be written to and read from the DB as events.
+///
+/// Instead of implementing this trait by hand, enable the `derive` feature and derive it.
+/// The event type, subject and source are declared using the `#[esdb(...)]` attribute, where the subject
+/// is a template that can reference fields of the type in curly braces.
+/// The data of the event is the type serialized using [serde], so it has to implement [`serde::Serialize`] and [`serde::Deserialize`].
+/// Structs may be generic, in which case the trait is implemented whenever the struct can be serialized and deserialized.
+/// The source is required just like the type and the subject, because the DB requires a source for every event
+/// and neither the client nor [`EventCandidate`] provides a default for it.
+///
+/// Deriving the trait also implements [`TryFrom<&Event>`](TryFrom) and [`TryFrom<T>`](TryFrom) for [`EventCandidate`] for the type,
+/// and adds the `EVENT_TYPE` and `SUBJECT_TEMPLATE` constants to structs.
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// use eventsourcingdb::event::{EventCandidate, EventSourcingDbEvent};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(EventSourcingDbEvent, Serialize, Deserialize)]
+/// #[esdb(
+///     type = "io.eventsourcingdb.library.book-acquired",
+///     subject = "/books/{id}",
+///     source = "https://library.eventsourcingdb.io"
+/// )]
+/// struct BookAcquired {
+///     id: u32,
+///     title: String,
+/// }
+///
+/// let candidate = EventCandidate::try_from(BookAcquired { id: 42, title: "2001 - A Space Odyssey".to_string() })
+///     .expect("Failed to serialize the event data");
+/// assert_eq!(candidate.ty, BookAcquired::EVENT_TYPE);
+/// assert_eq!(candidate.subject, "/books/42");
+/// ```
+///
+/// For enums, every variant represents its own event type, which has to be set on the variant.
+/// The subject and source can be set on the enum and be overridden per variant.
+/// Variants can have named fields, wrap a single type or have no fields at all. For variants wrapping a type, the subject template references the fields of the wrapped type.
+/// The data of a variant with named fields honours `#[serde(rename_all = "...")]` and `#[serde(deny_unknown_fields)]` on the variant
+/// and `#[serde(rename_all_fields = "...")]` on the enum. Attributes that only rename variants or pick the representation of the enum are ignored,
+/// as the data never contains the variant; all other container attributes are rejected.
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// use eventsourcingdb::event::EventSourcingDbEvent;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(EventSourcingDbEvent, Serialize, Deserialize)]
+/// #[esdb(subject = "/books/{id}", source = "https://library.eventsourcingdb.io")]
+/// enum BookEvent {
+///     #[esdb(type = "io.eventsourcingdb.library.book-acquired")]
+///     Acquired { id: u32, title: String },
+///     #[esdb(type = "io.eventsourcingdb.library.book-borrowed", subject = "/books/{id}/loans")]
+///     Borrowed { id: u32, borrower: String },
+/// }
+///
+/// let event = BookEvent::Borrowed { id: 42, borrower: "Jane Doe".to_string() };
+/// assert_eq!(event.event_type(), "io.eventsourcingdb.library.book-borrowed");
+/// assert_eq!(event.subject(), "/books/42/loans");
+/// ```
+pub trait EventSourcingDbEvent: Sized {
+    /// All event types this Rust type represents.
+    const EVENT_TYPES: &'static [&'static str];
+
+    /// Get the event type of this value.
+    fn event_type(&self) -> &'static str;
+
+    /// Get the subject this value is written to.
+    fn subject(&self) -> String;
+
+    /// Get the source of the event.
+    fn source(&self) -> &'static str;
+
+    /// Get the data of the event.
+    ///
+    /// # Errors
+    /// Returns an error if the value can't be serialized to JSON.
+    fn data(&self) -> Result<Value, EventError>;
+
+    /// Create a value from an event read from the DB.
+    ///
+    /// # Errors
+    /// Returns an error if the type of the event is not one of [`EventSourcingDbEvent::EVENT_TYPES`]
+    /// or if the data of the event does not match.
+    fn from_event(event: &Event) -> Result<Self, EventError>;
+
+    /// Create an event candidate from this value that can be written to the DB.
+    ///
+    /// # Errors
+    /// Returns an error if the value can't be serialized to JSON.
+    fn to_event_candidate(&self) -> Result<EventCandidate, EventError> {
+        Ok(EventCandidate::builder()
+            .data(self.data()?)
+            .source(self.source())
+            .subject(self.subject())
+            .ty(self.event_type())
+            .build())
+    }
+}
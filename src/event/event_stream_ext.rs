@@ -0,0 +1,173 @@
+use ed25519_dalek::VerifyingKey;
+use futures::{Stream, StreamExt, future};
+
+use crate::{
+    error::{ClientError, EventError, IntegrityError},
+    event::{Event, SignatureVerification},
+};
+
+/// Adapters for the event streams returned by the client, like [`crate::client::Client::read_events`].
+pub trait EventStreamExt: Stream<Item = Result<Event, ClientError>> + Sized {
+    /// Verify the hash of every event and that it links to the hash of the event before it.
+    ///
+    /// The DB chains all events in the order they were written, so the linkage can only be checked for a stream
+    /// that contains every event, e.g. when reading `/` recursively. The first event of the stream is trusted to follow
+    /// its predecessor. When the chain breaks, an [`IntegrityError`] with the ID of the offending event is yielded
+    /// and the stream ends.
+    /// ```
+    /// use eventsourcingdb::{event::EventStreamExt, request_options::ReadEventsOptions};
+    /// use futures::StreamExt;
+    /// # tokio_test::block_on(async {
+    /// # let container = eventsourcingdb::container::Container::start_preview().await.unwrap();
+    /// let db_url = "http://localhost:3000/";
+    /// let api_token = "secrettoken";
+    /// # let db_url = container.get_base_url().await.unwrap();
+    /// # let api_token = container.get_api_token();
+    /// let client = eventsourcingdb::client::Client::new(db_url, api_token);
+    /// let options = ReadEventsOptions {
+    ///     recursive: true,
+    ///     ..Default::default()
+    /// };
+    /// let mut event_stream = client
+    ///     .read_events("/", Some(options))
+    ///     .await
+    ///     .expect("Failed to read events")
+    ///     .verify_chain();
+    /// while let Some(event) = event_stream.next().await {
+    ///     println!("Found verified event {:?}", event.expect("The event chain is broken"));
+    /// }
+    /// # })
+    /// ```
+    fn verify_chain(self) -> impl Stream<Item = Result<Event, ClientError>> {
+        self.scan(ChainVerifier::default(), |verifier, event| {
+            future::ready(verifier.verify(event))
+        })
+    }
+
+    /// Verify the signature of every event with the given key.
+    ///
+    /// Events with an invalid signature and events without a signature are yielded as errors, the stream continues after them.
+    /// Use [`EventStreamExt::verify_signatures_with`] to handle unsigned events differently.
+    /// ```
+    /// use eventsourcingdb::event::EventStreamExt;
+    /// use futures::StreamExt;
+    /// # tokio_test::block_on(async {
+    /// # let container = eventsourcingdb::container::Container::builder()
+    /// #     .with_image_tag("preview")
+    /// #     .with_signing_key()
+    /// #     .start()
+    /// #     .await
+    /// #     .unwrap();
+    /// let db_url = "http://localhost:3000/";
+    /// let api_token = "secrettoken";
+    /// # let db_url = container.get_base_url().await.unwrap();
+    /// # let api_token = container.get_api_token();
+    /// let client = eventsourcingdb::client::Client::new(db_url, api_token);
+    /// # let verifying_key = container.get_verifying_key().unwrap();
+    /// let mut event_stream = client
+    ///     .read_events("/", None)
+    ///     .await
+    ///     .expect("Failed to read events")
+    ///     .verify_signatures(verifying_key);
+    /// while let Some(event) = event_stream.next().await {
+    ///     println!("Found signed event {:?}", event.expect("The signature is invalid"));
+    /// }
+    /// # })
+    /// ```
+    fn verify_signatures(
+        self,
+        verifying_key: &VerifyingKey,
+    ) -> impl Stream<Item = Result<Event, ClientError>> {
+        self.verify_signatures_with(
+            SignatureVerification::builder()
+                .verifying_key(*verifying_key)
+                .build(),
+        )
+    }
+
+    /// Verify the signature of every event according to the given settings.
+    ///
+    /// Events with an invalid signature are yielded as errors, the stream continues after them.
+    fn verify_signatures_with(
+        self,
+        signature_verification: SignatureVerification,
+    ) -> impl Stream<Item = Result<Event, ClientError>> {
+        self.filter_map(move |event| future::ready(signature_verification.verify(event)))
+    }
+}
+
+impl<S: Stream<Item = Result<Event, ClientError>>> EventStreamExt for S {}
+
+/// The predecessor hash of the first event in the DB.
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// State of [`EventStreamExt::verify_chain`], also used by [`crate::audit::audit`].
+#[derive(Default)]
+pub(crate) struct ChainVerifier {
+    /// The ID and hash of the last verified event
+    predecessor: Option<(String, String)>,
+    /// Whether the chain broke, which ends the stream
+    broken: bool,
+}
+
+impl ChainVerifier {
+    /// Create a verifier for a stream that starts with the first event in the DB, which has to link to the genesis hash.
+    pub(crate) fn from_genesis() -> Self {
+        Self {
+            predecessor: Some(("genesis".to_string(), GENESIS_HASH.to_string())),
+            broken: false,
+        }
+    }
+
+    /// Verify the next item of the stream, returning `None` once the stream should end.
+    fn verify(&mut self, event: Result<Event, ClientError>) -> Option<Result<Event, ClientError>> {
+        if self.broken {
+            return None;
+        }
+        let Ok(event) = event else {
+            return Some(event);
+        };
+        if let Err(error) = Self::check_hash(&event).and_then(|()| self.check_link(&event)) {
+            self.broken = true;
+            return Some(Err(error.into()));
+        }
+        self.advance(&event);
+        Some(Ok(event))
+    }
+
+    /// Check that the hash of an event matches its content.
+    pub(crate) fn check_hash(event: &Event) -> Result<(), IntegrityError> {
+        match event.verify_hash() {
+            Err(EventError::HashVerificationFailed { expected, actual }) => {
+                Err(IntegrityError::InvalidHash {
+                    event_id: event.id().to_string(),
+                    expected,
+                    actual,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Check that an event links to the hash of the previous event.
+    pub(crate) fn check_link(&self, event: &Event) -> Result<(), IntegrityError> {
+        match &self.predecessor {
+            Some((predecessor_id, predecessor_hash))
+                if predecessor_hash != event.predecessorhash() =>
+            {
+                Err(IntegrityError::BrokenChain {
+                    event_id: event.id().to_string(),
+                    predecessor_id: predecessor_id.clone(),
+                    expected: predecessor_hash.clone(),
+                    actual: event.predecessorhash().to_string(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Continue the chain from an event, regardless of whether it passed the checks.
+    pub(crate) fn advance(&mut self, event: &Event) {
+        self.predecessor = Some((event.id().to_string(), event.hash().to_string()));
+    }
+}
@@ -3,3 +3,4 @@
 pub mod event;
 pub mod event_candidate;
 pub mod management_event;
+pub mod typed_event;
@@ -2,7 +2,7 @@ use chrono::{DateTime, Utc};
 use ed25519_dalek::{Signature, Verifier, VerifyingKey};
 #[cfg(feature = "polars")]
 use polars::{frame::DataFrame, prelude::*};
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::DeserializeOwned};
 use serde_json::value::{RawValue, Value};
 
 use crate::{
@@ -77,6 +77,16 @@ impl Event {
     pub fn data(&self) -> &Value {
         &self.data.parsed
     }
+    /// Deserialize the data of an event into the given type.
+    ///
+    /// The data is deserialized from the raw JSON as sent by the DB.
+    /// To keep the deserialized data together with the event, use [`crate::event::TypedEvent`] instead.
+    ///
+    /// # Errors
+    /// Returns an error if the data does not match the given type.
+    pub fn data_as<T: DeserializeOwned>(&self) -> Result<T, EventError> {
+        Ok(serde_json::from_str(self.data.raw.get())?)
+    }
     /// Get the data content type of an event.
     #[must_use]
     pub fn datacontenttype(&self) -> &str {
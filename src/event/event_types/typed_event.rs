@@ -0,0 +1,101 @@
+use std::ops::Deref;
+
+use serde::{Serialize, Serializer, de::DeserializeOwned};
+
+use crate::{error::EventError, event::Event};
+
+/// Represents an event whose data has been deserialized into a Rust type.
+///
+/// The original [`Event`] including its raw data is kept, so [`Event::verify_hash`] and [`Event::verify_signature`]
+/// still work on the data exactly as it was sent by the DB.
+/// All other fields of the event are available through [`Deref`].
+/// ```
+/// use eventsourcingdb::event::{EventCandidate, TypedEvent};
+/// use futures::TryStreamExt;
+/// # use serde_json::json;
+/// #[derive(serde::Deserialize)]
+/// struct BookAcquired {
+///     title: String,
+/// }
+/// # tokio_test::block_on(async {
+/// # let container = eventsourcingdb::container::Container::start_preview().await.unwrap();
+/// let db_url = "http://localhost:3000/";
+/// let api_token = "secrettoken";
+/// # let db_url = container.get_base_url().await.unwrap();
+/// # let api_token = container.get_api_token();
+/// let client = eventsourcingdb::client::Client::new(db_url, api_token);
+/// let candidates = vec![
+///     EventCandidate::builder()
+///        .source("https://www.eventsourcingdb.io".to_string())
+///        .data(json!({"title": "2001 - A Space Odyssey"}))
+///        .subject("/books/42".to_string())
+///        .ty("io.eventsourcingdb.library.book-acquired".to_string())
+///        .build()
+/// ];
+/// let written_events = client.write_events(candidates, vec![]).await.expect("Failed to write events");
+/// let event: TypedEvent<BookAcquired> = written_events[0].clone().try_into().expect("Failed to deserialize data");
+/// assert_eq!(event.data().title, "2001 - A Space Odyssey");
+/// event.verify_hash().expect("Hash verification failed");
+/// # })
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypedEvent<T> {
+    event: Event,
+    data: T,
+}
+
+impl<T> TypedEvent<T> {
+    /// Get the deserialized data of the event.
+    #[must_use]
+    pub fn data(&self) -> &T {
+        &self.data
+    }
+    /// Get the original event.
+    #[must_use]
+    pub fn event(&self) -> &Event {
+        &self.event
+    }
+    /// Split the typed event into the original event and the deserialized data.
+    #[must_use]
+    pub fn into_parts(self) -> (Event, T) {
+        (self.event, self.data)
+    }
+    /// Take the deserialized data out of the typed event.
+    #[must_use]
+    pub fn into_data(self) -> T {
+        self.data
+    }
+}
+
+impl<T> Deref for TypedEvent<T> {
+    type Target = Event;
+
+    fn deref(&self) -> &Self::Target {
+        &self.event
+    }
+}
+
+impl<T: DeserializeOwned> TryFrom<Event> for TypedEvent<T> {
+    type Error = EventError;
+
+    fn try_from(event: Event) -> Result<Self, Self::Error> {
+        let data = event.data_as()?;
+        Ok(Self { event, data })
+    }
+}
+
+impl<T> From<TypedEvent<T>> for Event {
+    fn from(event: TypedEvent<T>) -> Self {
+        event.event
+    }
+}
+
+impl<T> Serialize for TypedEvent<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // Serialize the original event, so the raw data is kept as is
+        self.event.serialize(serializer)
+    }
+}
@@ -0,0 +1,65 @@
+use ed25519_dalek::VerifyingKey;
+use typed_builder::TypedBuilder;
+
+use crate::{
+    error::{ClientError, EventError},
+    event::Event,
+};
+
+/// Decides what happens to events without a signature when signatures are verified.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum UnsignedEventPolicy {
+    /// Yield [`EventError::MissingSignature`] instead of the event
+    #[default]
+    Reject,
+    /// Drop the event from the stream
+    Skip,
+    /// Yield the event without verifying it
+    PassThrough,
+}
+
+/// Settings for verifying the signatures of events, see [`crate::event::EventStreamExt::verify_signatures_with`].
+/// ```
+/// # use eventsourcingdb::event::{SignatureVerification, UnsignedEventPolicy};
+/// # use ed25519_dalek::VerifyingKey;
+/// # let verifying_key = VerifyingKey::default();
+/// let signature_verification = SignatureVerification::builder()
+///     .verifying_key(verifying_key)
+///     .unsigned_events(UnsignedEventPolicy::Skip)
+///     .build();
+/// ```
+#[derive(Debug, Clone, TypedBuilder)]
+pub struct SignatureVerification {
+    /// The key to verify the signatures with.
+    pub verifying_key: VerifyingKey,
+    /// What happens to events without a signature.
+    ///
+    /// By default, they are rejected.
+    #[builder(default)]
+    pub unsigned_events: UnsignedEventPolicy,
+}
+
+impl SignatureVerification {
+    /// Verify the signature of the next item of a stream, returning `None` if the item should be dropped.
+    pub(crate) fn verify(
+        &self,
+        event: Result<Event, ClientError>,
+    ) -> Option<Result<Event, ClientError>> {
+        let Ok(event) = event else {
+            return Some(event);
+        };
+        if event.signature().is_none() {
+            return match self.unsigned_events {
+                UnsignedEventPolicy::Reject => Some(Err(EventError::MissingSignature.into())),
+                UnsignedEventPolicy::Skip => None,
+                UnsignedEventPolicy::PassThrough => Some(Ok(event)),
+            };
+        }
+        Some(
+            event
+                .verify_signature(&self.verifying_key)
+                .map(|()| event)
+                .map_err(ClientError::from),
+        )
+    }
+}
@@ -0,0 +1,10 @@
+//! Utilities for working with [EventQL](https://docs.eventsourcingdb.io/reference/eventql/) queries.
+//!
+//! Queries are built with [`EventQl`], which renders every literal escaped, so values from user input can be embedded safely.
+//! Queries written by hand can be checked for structural mistakes with [`check`] before they are sent to the DB, e.g. in unit tests.
+
+mod parser;
+mod query_builder;
+
+pub use parser::check;
+pub use query_builder::{EventQl, EventQlBuilder, EventQlQuery, EventQlSource, Expr, Order};
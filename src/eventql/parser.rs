@@ -0,0 +1,149 @@
+use std::{iter::Peekable, str::Chars};
+
+use crate::error::EventQlSyntaxError;
+
+/// Check the structure of an EventQL query without sending it to the DB.
+///
+/// The check is limited to what every [EventQL](https://docs.eventsourcingdb.io/reference/eventql/) query has in common:
+/// it starts with `FROM`, its strings are closed and its parentheses, brackets and braces are balanced
+/// and nested at most 64 levels deep. Clauses, operators and functions are left to the DB,
+/// so a query that passes the check may still be rejected by the DB.
+/// ```
+/// use eventsourcingdb::eventql;
+/// assert!(eventql::check("FROM e IN events WHERE e.type == \"io.eventsourcingdb.library.book-acquired\" PROJECT INTO e").is_ok());
+///
+/// let error = eventql::check("FROM e IN events\nWHERE (e.data.pages > 100\nPROJECT INTO e")
+///     .expect_err("Expected a syntax error");
+/// assert_eq!((error.line, error.column), (2, 7));
+/// ```
+///
+/// # Errors
+/// Returns the first structural error in the query.
+pub fn check(query: &str) -> Result<(), EventQlSyntaxError> {
+    let mut cursor = Cursor::new(query);
+    while cursor.peek().is_some_and(char::is_whitespace) {
+        let _ = cursor.next();
+    }
+    let (line, column) = cursor.position();
+    let mut keyword = String::new();
+    while let Some(character) = cursor.peek().filter(char::is_ascii_alphabetic) {
+        keyword.push(character);
+        let _ = cursor.next();
+    }
+    if !keyword.eq_ignore_ascii_case("FROM") {
+        return Err(EventQlSyntaxError::new(
+            line,
+            column,
+            "a query has to start with FROM",
+        ));
+    }
+
+    // The closing characters of the currently open brackets with the positions of their opening characters.
+    let mut open_brackets: Vec<(char, usize, usize)> = Vec::new();
+    loop {
+        let (line, column) = cursor.position();
+        let Some(character) = cursor.next() else {
+            break;
+        };
+        if character == '"' {
+            cursor.skip_string(line, column)?;
+        } else if let Some(&(_, closing)) =
+            BRACKETS.iter().find(|(opening, _)| *opening == character)
+        {
+            if open_brackets.len() == MAX_NESTING_DEPTH {
+                return Err(EventQlSyntaxError::new(
+                    line,
+                    column,
+                    format!("the query is nested more than {MAX_NESTING_DEPTH} levels deep"),
+                ));
+            }
+            open_brackets.push((closing, line, column));
+        } else if BRACKETS.iter().any(|(_, closing)| *closing == character) {
+            match open_brackets.pop() {
+                Some((expected, ..)) if expected == character => {}
+                Some((expected, ..)) => {
+                    return Err(EventQlSyntaxError::new(
+                        line,
+                        column,
+                        format!("expected '{expected}' but found '{character}'"),
+                    ));
+                }
+                None => {
+                    return Err(EventQlSyntaxError::new(
+                        line,
+                        column,
+                        format!("unexpected '{character}'"),
+                    ));
+                }
+            }
+        }
+    }
+    match open_brackets.pop() {
+        Some((closing, line, column)) => Err(EventQlSyntaxError::new(
+            line,
+            column,
+            format!("missing '{closing}' for this bracket"),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// How deeply parentheses, brackets and braces may be nested.
+const MAX_NESTING_DEPTH: usize = 64;
+
+const BRACKETS: &[(char, char)] = &[('(', ')'), ('[', ']'), ('{', '}')];
+
+/// Iterates over the characters of a query while keeping track of their positions.
+struct Cursor<'a> {
+    characters: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(query: &'a str) -> Self {
+        Self {
+            characters: query.chars().peekable(),
+            line: 1,
+            column: 1,
+        }
+    }
+
+    /// Get the line and column of the next character.
+    fn position(&self) -> (usize, usize) {
+        (self.line, self.column)
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.characters.peek().copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let character = self.characters.next()?;
+        if character == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(character)
+    }
+
+    /// Skip the rest of a string whose opening quote is at the given position.
+    ///
+    /// # Errors
+    /// Returns an error if the string is not closed.
+    fn skip_string(&mut self, line: usize, column: usize) -> Result<(), EventQlSyntaxError> {
+        loop {
+            match self.next() {
+                None => return Err(EventQlSyntaxError::new(line, column, "unterminated string")),
+                // Escaped characters go through `next` as well, so escaped line breaks are counted.
+                Some('\\') => {
+                    let _ = self.next();
+                }
+                Some('"') => return Ok(()),
+                Some(_) => {}
+            }
+        }
+    }
+}
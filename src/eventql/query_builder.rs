@@ -0,0 +1,501 @@
+use std::{fmt, ops::Not};
+
+use serde_json::Number;
+
+use crate::{client::Precondition, error::EventQlError};
+
+/// Entry point for building EventQL queries.
+///
+/// Literals are always rendered as escaped EventQL literals, so values from user input can't change the structure of the query.
+/// Names of variables, fields, functions and object keys are never escaped. Instead, building fails for names that are
+/// not identifiers, e.g. `book-id`, or that are reserved words, e.g. `order`.
+/// ```
+/// use eventsourcingdb::eventql::{EventQl, Expr, Order};
+/// # fn main() -> Result<(), eventsourcingdb::error::EventQlError> {
+/// let title = "2001 - A \"Space\" Odyssey";
+/// let query = EventQl::from("e")?
+///     .in_events()
+///     .where_(Expr::field("e.type")?.equals("io.eventsourcingdb.library.book-acquired"))
+///     .where_(Expr::field("e.data.title")?.equals(title))
+///     .order_by(Expr::field("e.time")?, Order::Descending)
+///     .top(10)
+///     .project_into(Expr::object([("id", Expr::field("e.data.id")?), ("title", Expr::field("e.data.title")?)])?);
+/// assert_eq!(
+///     query.as_str(),
+///     r#"FROM e IN events WHERE (e.type == "io.eventsourcingdb.library.book-acquired") AND (e.data.title == "2001 - A \"Space\" Odyssey") ORDER BY e.time DESC TOP 10 PROJECT INTO { id: e.data.id, title: e.data.title }"#
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct EventQl;
+
+impl EventQl {
+    /// Start a query that binds each element of its source to the given variable.
+    ///
+    /// # Errors
+    /// Returns an error if the variable is not a valid identifier.
+    pub fn from(variable: &str) -> Result<EventQlSource, EventQlError> {
+        Ok(EventQlSource {
+            variable: identifier(variable)?,
+        })
+    }
+}
+
+/// A query whose source is not chosen yet, see [`EventQl::from`].
+#[derive(Debug, Clone)]
+pub struct EventQlSource {
+    variable: String,
+}
+
+impl EventQlSource {
+    /// Query the events in the DB.
+    #[must_use]
+    pub fn in_events(self) -> EventQlBuilder {
+        EventQlBuilder::new(self.variable, "events".to_string())
+    }
+
+    /// Query the rows of another query.
+    #[must_use]
+    pub fn in_query(self, query: &EventQlQuery) -> EventQlBuilder {
+        EventQlBuilder::new(self.variable, format!("({query})"))
+    }
+}
+
+/// The order of the rows of a query
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    /// Order the rows ascending
+    Ascending,
+    /// Order the rows descending
+    Descending,
+}
+
+/// A query that is not projected yet.
+///
+/// Finish the query with [`EventQlBuilder::project_into`].
+#[derive(Debug, Clone)]
+pub struct EventQlBuilder {
+    variable: String,
+    source: String,
+    filter: Option<Expr>,
+    group_by: Option<Expr>,
+    order_by: Option<(Expr, Order)>,
+    skip: Option<u64>,
+    top: Option<u64>,
+}
+
+impl EventQlBuilder {
+    fn new(variable: String, source: String) -> Self {
+        Self {
+            variable,
+            source,
+            filter: None,
+            group_by: None,
+            order_by: None,
+            skip: None,
+            top: None,
+        }
+    }
+
+    /// Only keep the rows that match the condition.
+    ///
+    /// Calling this multiple times combines the conditions with `AND`.
+    #[must_use]
+    pub fn where_(mut self, condition: impl Into<Expr>) -> Self {
+        let condition = condition.into();
+        self.filter = Some(match self.filter {
+            Some(filter) => filter.and(condition),
+            None => condition,
+        });
+        self
+    }
+    /// Group the rows by the given expression.
+    #[must_use]
+    pub fn group_by(mut self, expression: impl Into<Expr>) -> Self {
+        self.group_by = Some(expression.into());
+        self
+    }
+    /// Order the rows by the given expression.
+    #[must_use]
+    pub fn order_by(mut self, expression: impl Into<Expr>, order: Order) -> Self {
+        self.order_by = Some((expression.into(), order));
+        self
+    }
+    /// Skip the given number of rows.
+    #[must_use]
+    pub fn skip(mut self, count: u64) -> Self {
+        self.skip = Some(count);
+        self
+    }
+    /// Only return the given number of rows.
+    #[must_use]
+    pub fn top(mut self, count: u64) -> Self {
+        self.top = Some(count);
+        self
+    }
+
+    /// Finish the query by choosing what each row contains.
+    #[must_use]
+    pub fn project_into(self, projection: impl Into<Expr>) -> EventQlQuery {
+        let mut clauses = vec![format!("FROM {} IN {}", self.variable, self.source)];
+        if let Some(filter) = self.filter {
+            clauses.push(format!("WHERE {filter}"));
+        }
+        if let Some(group_by) = self.group_by {
+            clauses.push(format!("GROUP BY {group_by}"));
+        }
+        if let Some((expression, order)) = self.order_by {
+            let order = match order {
+                Order::Ascending => "ASC",
+                Order::Descending => "DESC",
+            };
+            clauses.push(format!("ORDER BY {expression} {order}"));
+        }
+        if let Some(skip) = self.skip {
+            clauses.push(format!("SKIP {skip}"));
+        }
+        if let Some(top) = self.top {
+            clauses.push(format!("TOP {top}"));
+        }
+        clauses.push(format!("PROJECT INTO {}", projection.into()));
+        EventQlQuery(clauses.join(" "))
+    }
+}
+
+/// A rendered EventQL query.
+///
+/// Pass it to [`crate::client::Client::run_eventql_query`] via [`EventQlQuery::as_str`] or turn it into a [`Precondition`].
+/// ```
+/// use eventsourcingdb::{Precondition, eventql::{EventQl, Expr}};
+/// # fn main() -> Result<(), eventsourcingdb::error::EventQlError> {
+/// let precondition: Precondition = EventQl::from("e")?
+///     .in_events()
+///     .where_(Expr::field("e.subject")?.equals("/books/42"))
+///     .project_into(Expr::function("COUNT", [])?.equals(0))
+///     .into();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EventQlQuery(String);
+
+impl EventQlQuery {
+    /// Get the query as a string.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for EventQlQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<EventQlQuery> for String {
+    fn from(query: EventQlQuery) -> Self {
+        query.0
+    }
+}
+
+impl From<EventQlQuery> for Precondition {
+    fn from(query: EventQlQuery) -> Self {
+        Precondition::IsEventQLQueryTrue { query: query.0 }
+    }
+}
+
+/// An expression within an EventQL query.
+///
+/// Strings, numbers and booleans convert into literals, fields and function calls are created with the associated functions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expr(ExprKind);
+
+#[derive(Debug, Clone, PartialEq)]
+enum ExprKind {
+    Field(String),
+    String(String),
+    Number(Number),
+    Bool(bool),
+    Null,
+    Binary {
+        left: Box<Expr>,
+        operator: &'static str,
+        right: Box<Expr>,
+    },
+    Not(Box<Expr>),
+    Function {
+        name: String,
+        arguments: Vec<Expr>,
+    },
+    Object(Vec<(String, Expr)>),
+    Array(Vec<Expr>),
+}
+
+impl Expr {
+    /// Access a variable or one of its fields, e.g. `e.data.title`.
+    ///
+    /// # Errors
+    /// Returns an error if the variable or one of the fields is not a valid identifier.
+    pub fn field(path: &str) -> Result<Self, EventQlError> {
+        for segment in path.split('.') {
+            let _ = identifier(segment)?;
+        }
+        Ok(Self(ExprKind::Field(path.to_string())))
+    }
+    /// Call a function, e.g. `COUNT()`.
+    ///
+    /// # Errors
+    /// Returns an error if the name is not a valid identifier.
+    pub fn function(
+        name: &str,
+        arguments: impl IntoIterator<Item = Expr>,
+    ) -> Result<Self, EventQlError> {
+        Ok(Self(ExprKind::Function {
+            name: identifier(name)?,
+            arguments: arguments.into_iter().collect(),
+        }))
+    }
+    /// Create an object with the given fields, e.g. `{ id: e.id }`.
+    ///
+    /// # Errors
+    /// Returns an error if one of the keys is not a valid identifier.
+    pub fn object<'a>(
+        fields: impl IntoIterator<Item = (&'a str, Expr)>,
+    ) -> Result<Self, EventQlError> {
+        Ok(Self(ExprKind::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| Ok((identifier(key)?, value)))
+                .collect::<Result<_, EventQlError>>()?,
+        )))
+    }
+    /// Create an array of the given elements.
+    #[must_use]
+    pub fn array(elements: impl IntoIterator<Item = Expr>) -> Self {
+        Self(ExprKind::Array(elements.into_iter().collect()))
+    }
+    /// The `null` literal.
+    #[must_use]
+    pub fn null() -> Self {
+        Self(ExprKind::Null)
+    }
+
+    fn binary(self, operator: &'static str, right: impl Into<Expr>) -> Self {
+        Self(ExprKind::Binary {
+            left: Box::new(self),
+            operator,
+            right: Box::new(right.into()),
+        })
+    }
+
+    /// Compare with `==`.
+    #[must_use]
+    pub fn equals(self, other: impl Into<Expr>) -> Self {
+        self.binary("==", other)
+    }
+    /// Compare with `!=`.
+    #[must_use]
+    pub fn not_equals(self, other: impl Into<Expr>) -> Self {
+        self.binary("!=", other)
+    }
+    /// Compare with `<`.
+    #[must_use]
+    pub fn less_than(self, other: impl Into<Expr>) -> Self {
+        self.binary("<", other)
+    }
+    /// Compare with `<=`.
+    #[must_use]
+    pub fn less_than_or_equal(self, other: impl Into<Expr>) -> Self {
+        self.binary("<=", other)
+    }
+    /// Compare with `>`.
+    #[must_use]
+    pub fn greater_than(self, other: impl Into<Expr>) -> Self {
+        self.binary(">", other)
+    }
+    /// Compare with `>=`.
+    #[must_use]
+    pub fn greater_than_or_equal(self, other: impl Into<Expr>) -> Self {
+        self.binary(">=", other)
+    }
+    /// Combine with `AND`.
+    #[must_use]
+    pub fn and(self, other: impl Into<Expr>) -> Self {
+        self.binary("AND", other)
+    }
+    /// Combine with `OR`.
+    #[must_use]
+    pub fn or(self, other: impl Into<Expr>) -> Self {
+        self.binary("OR", other)
+    }
+
+    /// Utility function to render an operand, wrapping operations in parentheses.
+    fn fmt_operand(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            ExprKind::Binary { .. } | ExprKind::Not(_) => write!(f, "({self})"),
+            _ => write!(f, "{self}"),
+        }
+    }
+}
+
+impl Not for Expr {
+    type Output = Expr;
+
+    fn not(self) -> Expr {
+        Self(ExprKind::Not(Box::new(self)))
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            ExprKind::Field(path) => f.write_str(path),
+            ExprKind::String(value) => f.write_str(&string_literal(value)),
+            ExprKind::Number(value) => write!(f, "{value}"),
+            ExprKind::Bool(value) => write!(f, "{value}"),
+            ExprKind::Null => f.write_str("null"),
+            ExprKind::Binary {
+                left,
+                operator,
+                right,
+            } => {
+                // AND chains don't need parentheses, which keeps combined conditions readable.
+                if *operator == "AND"
+                    && matches!(
+                        &left.0,
+                        ExprKind::Binary {
+                            operator: "AND",
+                            ..
+                        }
+                    )
+                {
+                    write!(f, "{left}")?;
+                } else {
+                    left.fmt_operand(f)?;
+                }
+                write!(f, " {operator} ")?;
+                right.fmt_operand(f)
+            }
+            ExprKind::Not(operand) => {
+                f.write_str("NOT ")?;
+                operand.fmt_operand(f)
+            }
+            ExprKind::Function { name, arguments } => {
+                write!(f, "{name}(")?;
+                for (index, argument) in arguments.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{argument}")?;
+                }
+                f.write_str(")")
+            }
+            ExprKind::Object(fields) => {
+                f.write_str("{ ")?;
+                for (index, (key, value)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{key}: {value}")?;
+                }
+                f.write_str(" }")
+            }
+            ExprKind::Array(elements) => {
+                f.write_str("[")?;
+                for (index, element) in elements.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{element}")?;
+                }
+                f.write_str("]")
+            }
+        }
+    }
+}
+
+impl From<&str> for Expr {
+    fn from(value: &str) -> Self {
+        Self(ExprKind::String(value.to_string()))
+    }
+}
+
+impl From<String> for Expr {
+    fn from(value: String) -> Self {
+        Self(ExprKind::String(value))
+    }
+}
+
+impl From<bool> for Expr {
+    fn from(value: bool) -> Self {
+        Self(ExprKind::Bool(value))
+    }
+}
+
+impl From<i64> for Expr {
+    fn from(value: i64) -> Self {
+        Self(ExprKind::Number(value.into()))
+    }
+}
+
+impl From<u64> for Expr {
+    fn from(value: u64) -> Self {
+        Self(ExprKind::Number(value.into()))
+    }
+}
+
+impl From<i32> for Expr {
+    fn from(value: i32) -> Self {
+        Self(ExprKind::Number(value.into()))
+    }
+}
+
+impl TryFrom<f64> for Expr {
+    type Error = EventQlError;
+
+    /// Convert a number into a literal.
+    ///
+    /// # Errors
+    /// Returns an error for NaN and infinite numbers, as they have no literal.
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        Number::from_f64(value)
+            .map(|number| Self(ExprKind::Number(number)))
+            .ok_or(EventQlError::NonFiniteNumber { value })
+    }
+}
+
+/// The reserved words of EventQL, which can't be used as identifiers regardless of their case.
+const KEYWORDS: &[&str] = &[
+    "FROM", "IN", "WHERE", "GROUP", "BY", "HAVING", "ORDER", "ASC", "DESC", "SKIP", "TOP",
+    "PROJECT", "INTO", "DISTINCT", "AND", "OR", "XOR", "NOT", "TRUE", "FALSE", "NULL",
+];
+
+/// Utility function to ensure a name can be used as an identifier in a query.
+///
+/// # Errors
+/// Returns an error if the name is not a valid identifier or if it is a reserved word.
+fn identifier(name: &str) -> Result<String, EventQlError> {
+    let mut characters = name.chars();
+    let is_identifier = characters
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && characters.all(|character| character.is_ascii_alphanumeric() || character == '_');
+    let is_keyword = KEYWORDS
+        .iter()
+        .any(|keyword| name.eq_ignore_ascii_case(keyword));
+    if is_identifier && !is_keyword {
+        Ok(name.to_string())
+    } else {
+        Err(EventQlError::InvalidIdentifier {
+            name: name.to_string(),
+        })
+    }
+}
+
+/// Utility function to render a string as an escaped string literal.
+fn string_literal(value: &str) -> String {
+    // JSON string escaping is a valid EventQL string literal.
+    serde_json::Value::String(value.to_string()).to_string()
+}
@@ -0,0 +1,55 @@
+//! Utilities shared by the stores that keep one file per key in a directory.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+use tokio::{fs::File, io::AsyncWriteExt};
+
+/// Get the file of a key within a directory.
+///
+/// Keys may contain slashes and be longer than file systems allow for file names,
+/// so the file is named after the hex encoded SHA-256 hash of the key, which always has 64 characters.
+pub(crate) fn key_path(directory: &Path, key: &str, extension: &str) -> PathBuf {
+    directory.join(format!("{}.{extension}", hex::encode(Sha256::digest(key))))
+}
+
+/// Replace the content of a file in a way that survives crashes and power losses.
+///
+/// The content is written to a temporary file next to the target and flushed to disk before it is renamed to the target.
+/// Afterwards, the directory is flushed as well, so the rename can't get lost either.
+/// The directory is created if it doesn't exist yet.
+///
+/// # Errors
+/// Returns an error if any of the file system operations fails.
+pub(crate) async fn write_atomically(path: &Path, content: &[u8]) -> io::Result<()> {
+    let directory = path.parent().unwrap_or_else(|| Path::new("."));
+    tokio::fs::create_dir_all(directory).await?;
+
+    let mut temporary_name = path.file_name().unwrap_or_default().to_os_string();
+    temporary_name.push(".tmp");
+    let temporary_path = path.with_file_name(temporary_name);
+    let mut file = File::create(&temporary_path).await?;
+    file.write_all(content).await?;
+    file.sync_all().await?;
+    drop(file);
+
+    tokio::fs::rename(&temporary_path, path).await?;
+    sync_directory(directory).await
+}
+
+/// Utility function to flush the entries of a directory to disk.
+#[cfg(unix)]
+async fn sync_directory(directory: &Path) -> io::Result<()> {
+    File::open(directory).await?.sync_all().await
+}
+
+/// Utility function to flush the entries of a directory to disk.
+///
+/// Directories can't be opened as files on other platforms, so only the file itself is flushed there.
+#[cfg(not(unix))]
+async fn sync_directory(_directory: &Path) -> io::Result<()> {
+    Ok(())
+}
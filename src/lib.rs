@@ -47,11 +47,28 @@
     warnings
 )]
 
+pub mod aggregate;
+pub mod audit;
 pub mod client;
 #[cfg(feature = "testcontainer")]
 pub mod container;
 pub mod error;
 pub mod event;
+pub mod eventql;
+mod file_store;
+pub mod migration;
+pub mod ndjson;
+pub mod projection;
+pub mod subject;
 
-pub use client::{Client, Precondition, request_options};
-pub use event::{Event, EventCandidate, ManagementEvent, TraceInfo};
+pub use client::{Client, Precondition, Preconditions, request_options};
+pub use event::{Event, EventCandidate, ManagementEvent, TraceInfo, TypedEvent};
+pub use subject::Subject;
+
+/// Reexports used by the code generated by the derive macros. This is not part of the public API.
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub mod __private {
+    pub use serde;
+    pub use serde_json;
+}
@@ -0,0 +1,300 @@
+//! Migration of events from one DB instance to another.
+//!
+//! The events of the selected subjects are replayed in the order they were written to the source DB, also across subjects,
+//! so the target DB ends up with the same global order. Events are streamed from the source and written in batches, each
+//! guarded by preconditions that require every subject to be pristine or to still be on its last migrated event.
+//! Running a migration again resumes it: the events a subject already has in the target DB are taken as its first migrated
+//! events, so only the remaining events are written and nothing is duplicated. This requires that the migrated subjects of the
+//! target DB are written by the migration only and that the transformation drops the same events on every run.
+//! The migrated events get new IDs, times and hashes, while their subjects, types, sources, data and trace info are preserved
+//! unless they are changed by a transformation.
+//! ```
+//! use eventsourcingdb::migration::{MigrationOptions, migrate_with};
+//! # tokio_test::block_on(async {
+//! # let source_container = eventsourcingdb::container::Container::start_preview().await.unwrap();
+//! # let target_container = eventsourcingdb::container::Container::start_preview().await.unwrap();
+//! # let source = source_container.get_client().await.unwrap();
+//! # let target = target_container.get_client().await.unwrap();
+//! let options = MigrationOptions { recursive: true };
+//! let report = migrate_with(&source, &target, &["/books"], options, |mut event| {
+//!     event.ty = event.ty.replace("io.eventsourcingdb.library", "io.eventsourcingdb.archive");
+//!     if let Some(data) = event.data.as_object_mut() {
+//!         let _ = data.remove("borrower");
+//!     }
+//!     Some(event)
+//! })
+//! .await
+//! .expect("Failed to migrate events");
+//! println!("Migrated {} events", report.event_count);
+//! # })
+//! ```
+
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet},
+    mem,
+};
+
+use futures::{StreamExt, stream::BoxStream};
+
+use crate::{
+    Precondition, Preconditions,
+    client::Client,
+    error::ClientError,
+    event::{Event, EventCandidate},
+    request_options::ReadEventsOptions,
+};
+
+/// The maximum number of events written at once.
+const BATCH_SIZE: usize = 1000;
+
+/// Options for migrating events
+#[derive(Debug, Clone, Default)]
+pub struct MigrationOptions {
+    /// Include the nested subjects of the selected subjects
+    pub recursive: bool,
+}
+
+/// The result of a migration.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// The subjects events were written to
+    pub migrated_subjects: Vec<String>,
+    /// The subjects that were skipped because the target DB already has all of their events, e.g. from a previous run
+    pub skipped_subjects: Vec<String>,
+    /// The number of copied events
+    pub event_count: u64,
+}
+
+/// Copy the events of the selected subjects from the source to the target DB.
+///
+/// # Errors
+/// Returns an error if reading or writing the events fails, see [`migrate_with`].
+pub async fn migrate(
+    source: &Client,
+    target: &Client,
+    subjects: &[&str],
+    options: MigrationOptions,
+) -> Result<MigrationReport, ClientError> {
+    migrate_with(source, target, subjects, options, Some).await
+}
+
+/// Copy the events of the selected subjects from the source to the target DB and transform them on the way.
+///
+/// The transformation is called for every event in the order the events were written to the source DB.
+/// It can change every part of an event, e.g. to rename event types or to redact data, or drop an event by returning `None`.
+/// The subjects the transformed events end up in are the ones that are resumed when running the migration again.
+///
+/// The selected subjects are read at the same time and merged by event ID, while the target DB is read once up front
+/// to count the events each subject already has there. Only one batch of events is held in memory at a time.
+///
+/// # Errors
+/// Returns an error if reading or writing the events fails, including if another client writes to one of the migrated subjects
+/// in the meantime. Batches written before the error are kept in the target DB and are not written again when running
+/// the migration again.
+pub async fn migrate_with<F>(
+    source: &Client,
+    target: &Client,
+    subjects: &[&str],
+    options: MigrationOptions,
+    mut transform: F,
+) -> Result<MigrationReport, ClientError>
+where
+    F: FnMut(EventCandidate) -> Option<EventCandidate>,
+{
+    let mut targets = target_subjects(target).await?;
+    let mut events = OrderedEvents::open(source, subjects, options.recursive).await?;
+    let mut migration = Migration::default();
+    while let Some(event) = events.next().await? {
+        let Some(candidate) = transform(event.into()) else {
+            continue;
+        };
+        let subject = targets.entry(candidate.subject.clone()).or_default();
+        if subject.skipped < subject.existing {
+            subject.skipped += 1;
+            continue;
+        }
+        migration.batch.push(candidate);
+        if migration.batch.len() == BATCH_SIZE {
+            migration.write(target, &mut targets).await?;
+        }
+    }
+    migration.write(target, &mut targets).await?;
+
+    let skipped_subjects = targets
+        .into_iter()
+        .filter(|(subject, state)| {
+            state.skipped > 0 && !migration.migrated_subjects.contains(subject)
+        })
+        .map(|(subject, _)| subject)
+        .collect();
+    Ok(MigrationReport {
+        migrated_subjects: migration.migrated_subjects.into_iter().collect(),
+        skipped_subjects,
+        event_count: migration.event_count,
+    })
+}
+
+/// The state of a subject in the target DB.
+#[derive(Debug, Default)]
+struct TargetSubject {
+    /// The number of events the subject had in the target DB before the migration started
+    existing: u64,
+    /// The number of migrated events that were skipped because they are among the existing events
+    skipped: u64,
+    /// The ID of the last event of the subject in the target DB
+    last_event_id: Option<String>,
+}
+
+/// The progress of a running migration.
+#[derive(Debug, Default)]
+struct Migration {
+    batch: Vec<EventCandidate>,
+    migrated_subjects: BTreeSet<String>,
+    event_count: u64,
+}
+
+impl Migration {
+    /// Write the current batch, requiring each of its subjects to still be on the last event known for it.
+    ///
+    /// # Errors
+    /// Returns an error if writing the events fails.
+    async fn write(
+        &mut self,
+        target: &Client,
+        targets: &mut BTreeMap<String, TargetSubject>,
+    ) -> Result<(), ClientError> {
+        if self.batch.is_empty() {
+            return Ok(());
+        }
+        let batch = mem::take(&mut self.batch);
+        let batch_subjects: BTreeSet<&str> = batch
+            .iter()
+            .map(|candidate| candidate.subject.as_str())
+            .collect();
+        let mut preconditions = Preconditions::new();
+        for subject in batch_subjects {
+            preconditions = match targets
+                .get(subject)
+                .and_then(|state| state.last_event_id.as_ref())
+            {
+                Some(event_id) => {
+                    preconditions.with_precondition(Precondition::IsSubjectOnEventId {
+                        subject: subject.to_string(),
+                        event_id: event_id.clone(),
+                    })
+                }
+                None => preconditions.with_subject_pristine(subject),
+            };
+        }
+        let written = target.write_events(batch, preconditions.build()).await?;
+        self.event_count += written.len() as u64;
+        for event in written {
+            targets
+                .entry(event.subject().to_string())
+                .or_default()
+                .last_event_id = Some(event.id().to_string());
+            let _ = self.migrated_subjects.insert(event.subject().to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Utility function to count the events of every subject in the target DB and find the last one of each, reading the DB once.
+///
+/// # Errors
+/// Returns an error if reading the events fails.
+async fn target_subjects(target: &Client) -> Result<BTreeMap<String, TargetSubject>, ClientError> {
+    let options = ReadEventsOptions {
+        recursive: true,
+        ..Default::default()
+    };
+    let mut events = target.read_events_unverified("/", Some(options)).await?;
+    let mut subjects: BTreeMap<String, TargetSubject> = BTreeMap::new();
+    while let Some(event) = events.next().await {
+        let event = event?;
+        let subject = subjects.entry(event.subject().to_string()).or_default();
+        subject.existing += 1;
+        subject.last_event_id = Some(event.id().to_string());
+    }
+    Ok(subjects)
+}
+
+/// The events of several subjects, merged into the order they were written.
+struct OrderedEvents<'a> {
+    subjects: Vec<SubjectEvents<'a>>,
+    /// The ID of the last returned event, to skip events of overlapping subjects
+    last_event_id: Option<String>,
+}
+
+/// The events of one selected subject, with the next event already read to compare it with the other subjects.
+struct SubjectEvents<'a> {
+    events: BoxStream<'a, Result<Event, ClientError>>,
+    next: Option<Event>,
+}
+
+impl<'a> OrderedEvents<'a> {
+    /// Start reading the events of the selected subjects.
+    ///
+    /// # Errors
+    /// Returns an error if reading the events fails.
+    async fn open(
+        source: &'a Client,
+        subjects: &[&'a str],
+        recursive: bool,
+    ) -> Result<Self, ClientError> {
+        let mut subject_events = Vec::with_capacity(subjects.len());
+        for &subject in subjects {
+            let options = ReadEventsOptions {
+                recursive,
+                ..Default::default()
+            };
+            let mut events = source.read_events(subject, Some(options)).await?.boxed();
+            let next = events.next().await.transpose()?;
+            subject_events.push(SubjectEvents { events, next });
+        }
+        Ok(Self {
+            subjects: subject_events,
+            last_event_id: None,
+        })
+    }
+
+    /// Get the next event across all subjects, or `None` once all events are read.
+    ///
+    /// # Errors
+    /// Returns an error if reading the events fails.
+    async fn next(&mut self) -> Result<Option<Event>, ClientError> {
+        loop {
+            let Some(subject) = self
+                .subjects
+                .iter_mut()
+                .filter(|subject| subject.next.is_some())
+                .min_by(|a, b| match (&a.next, &b.next) {
+                    (Some(a), Some(b)) => compare_event_ids(a.id(), b.id()),
+                    _ => Ordering::Equal,
+                })
+            else {
+                return Ok(None);
+            };
+            let following = subject.events.next().await.transpose()?;
+            let Some(event) = mem::replace(&mut subject.next, following) else {
+                return Ok(None);
+            };
+            if self.last_event_id.as_deref() == Some(event.id()) {
+                continue;
+            }
+            self.last_event_id = Some(event.id().to_string());
+            return Ok(Some(event));
+        }
+    }
+}
+
+/// Utility function to compare event IDs by the order the events were written.
+///
+/// The DB assigns ascending integer IDs, so they are compared as numbers. IDs that are not numbers are compared as strings.
+fn compare_event_ids(a: &str, b: &str) -> Ordering {
+    match (a.parse::<u64>(), b.parse::<u64>()) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        _ => a.cmp(b),
+    }
+}
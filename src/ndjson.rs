@@ -0,0 +1,117 @@
+//! Export and import of events as [NDJSON](https://github.com/ndjson/ndjson-spec), one event per line.
+//!
+//! Exported events contain all their fields, including the hash, the signature and the trace info.
+//! When importing them into another DB, the events are written anew, so they get new IDs, times and hashes
+//! while their subjects, types, sources, data and trace info are preserved.
+//! ```
+//! use eventsourcingdb::{ndjson, request_options::ReadEventsOptions};
+//! # tokio_test::block_on(async {
+//! # let container = eventsourcingdb::container::Container::start_preview().await.unwrap();
+//! let db_url = "http://localhost:3000/";
+//! let api_token = "secrettoken";
+//! # let db_url = container.get_base_url().await.unwrap();
+//! # let api_token = container.get_api_token();
+//! let client = eventsourcingdb::client::Client::new(db_url, api_token);
+//! let options = ReadEventsOptions {
+//!     recursive: true,
+//!     ..Default::default()
+//! };
+//! let mut backup = Vec::new();
+//! let exported = ndjson::export_events(&client, "/books", Some(options), &mut backup)
+//!     .await
+//!     .expect("Failed to export events");
+//! let imported = ndjson::import_events(&client, backup.as_slice(), ndjson::ImportOptions::default())
+//!     .await
+//!     .expect("Failed to import events");
+//! assert_eq!(exported, imported);
+//! # })
+//! ```
+
+use futures::StreamExt;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::{
+    client::Client,
+    error::ClientError,
+    event::{Event, EventCandidate},
+    request_options::ReadEventsOptions,
+};
+
+/// Options for importing events
+#[derive(Debug, Clone)]
+pub struct ImportOptions {
+    /// The maximum number of events written at once
+    pub batch_size: usize,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self { batch_size: 100 }
+    }
+}
+
+/// Write the events of a subject to a writer as NDJSON.
+///
+/// Returns the number of exported events.
+///
+/// # Errors
+/// Returns an error if reading the events or writing to the writer fails.
+pub async fn export_events<W: AsyncWrite + Unpin>(
+    client: &Client,
+    subject: &str,
+    options: Option<ReadEventsOptions<'_>>,
+    writer: &mut W,
+) -> Result<u64, ClientError> {
+    let mut events = client.read_events(subject, options).await?;
+    let mut count = 0;
+    while let Some(event) = events.next().await {
+        let mut line = serde_json::to_vec(&event?)?;
+        line.push(b'\n');
+        writer.write_all(&line).await?;
+        count += 1;
+    }
+    writer.flush().await?;
+    Ok(count)
+}
+
+/// Read events from NDJSON and write them to the DB in batches.
+///
+/// Empty lines are skipped. Returns the number of imported events.
+///
+/// # Errors
+/// Returns an error if reading from the reader fails, a line is not a valid event or writing the events fails.
+/// Batches written before the error are not rolled back.
+pub async fn import_events<R: AsyncBufRead + Unpin>(
+    client: &Client,
+    reader: R,
+    options: ImportOptions,
+) -> Result<u64, ClientError> {
+    let batch_size = options.batch_size.max(1);
+    let mut lines = reader.lines();
+    let mut batch: Vec<EventCandidate> = Vec::with_capacity(batch_size);
+    let mut count = 0;
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let event: Event = serde_json::from_str(&line)?;
+        batch.push(event.into());
+        if batch.len() == batch_size {
+            count += write_batch(client, &mut batch).await?;
+        }
+    }
+    count += write_batch(client, &mut batch).await?;
+    Ok(count)
+}
+
+/// Utility function to write a batch of events and empty it.
+///
+/// # Errors
+/// Returns an error if writing the events fails.
+async fn write_batch(client: &Client, batch: &mut Vec<EventCandidate>) -> Result<u64, ClientError> {
+    if batch.is_empty() {
+        return Ok(0);
+    }
+    let written = client.write_events(std::mem::take(batch), vec![]).await?;
+    Ok(written.len() as u64)
+}
@@ -0,0 +1,162 @@
+//! Projections that keep read models up to date with the events of a subject tree.
+//!
+//! A [`Projection`] processes events one by one. The [`ProjectionRunner`] observes the events of a subject and its nested subjects,
+//! passes them to the projection and stores the ID of the last processed event in a [`CheckpointStore`].
+//! When the runner starts again, e.g. after a restart of the process, it continues after this checkpoint.
+//! ```
+//! use eventsourcingdb::{
+//!     event::Event,
+//!     projection::{InMemoryCheckpointStore, Projection, ProjectionRunner},
+//! };
+//! # use std::time::Duration;
+//! #[derive(Debug, Default)]
+//! struct BorrowedBooks {
+//!     count: usize,
+//! }
+//!
+//! impl Projection for BorrowedBooks {
+//!     type Error = std::convert::Infallible;
+//!
+//!     async fn apply(&mut self, event: &Event) -> Result<(), Self::Error> {
+//!         match event.ty() {
+//!             "io.eventsourcingdb.library.book-borrowed" => self.count += 1,
+//!             "io.eventsourcingdb.library.book-returned" => self.count -= 1,
+//!             _ => {}
+//!         }
+//!         Ok(())
+//!     }
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! # let container = eventsourcingdb::container::Container::start_preview().await.unwrap();
+//! let db_url = "http://localhost:3000/";
+//! let api_token = "secrettoken";
+//! # let db_url = container.get_base_url().await.unwrap();
+//! # let api_token = container.get_api_token();
+//! let client = eventsourcingdb::client::Client::new(db_url, api_token);
+//! let mut runner = ProjectionRunner::builder()
+//!     .client(client)
+//!     .name("borrowed-books")
+//!     .subject("/books")
+//!     .projection(BorrowedBooks::default())
+//!     .checkpoint_store(InMemoryCheckpointStore::new())
+//!     .build();
+//! # let _ = tokio::time::timeout(Duration::from_millis(100), async {
+//! runner.run().await.expect("Failed to run projection");
+//! # }).await;
+//! # })
+//! ```
+
+mod checkpoint_store;
+
+use std::future::Future;
+
+use futures::StreamExt;
+use typed_builder::TypedBuilder;
+
+pub use checkpoint_store::{CheckpointStore, FileCheckpointStore, InMemoryCheckpointStore};
+
+use crate::{
+    client::{Client, RetryPolicy},
+    error::ProjectionError,
+    event::Event,
+    request_options::{Bound, BoundType, ObserveEventsOptions},
+};
+
+/// Represents a read model that is built from events.
+pub trait Projection: Send {
+    /// The error returned if the projection can't process an event.
+    type Error;
+
+    /// Process the next event.
+    ///
+    /// # Errors
+    /// Returns an error if the event can't be processed. The runner stops without moving the checkpoint past the event.
+    fn apply(&mut self, event: &Event) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
+
+/// Feeds the events of a subject tree into a projection and keeps track of its progress.
+#[derive(Debug, TypedBuilder)]
+pub struct ProjectionRunner<P, S> {
+    /// The client to observe the events with.
+    client: Client,
+    /// The name the checkpoint of the projection is stored under.
+    #[builder(setter(into))]
+    name: String,
+    /// The subject to observe, including its nested subjects.
+    #[builder(setter(into))]
+    subject: String,
+    /// The projection the events are passed to.
+    projection: P,
+    /// The store for the checkpoint of the projection.
+    checkpoint_store: S,
+    /// The policy for reconnecting to the DB when the connection is lost.
+    #[builder(default)]
+    retry_policy: RetryPolicy,
+}
+
+impl<P: Projection, S: CheckpointStore> ProjectionRunner<P, S> {
+    /// Get the name the checkpoint of the projection is stored under.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Get the subject the runner observes.
+    #[must_use]
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+    /// Get the projection.
+    #[must_use]
+    pub fn projection(&self) -> &P {
+        &self.projection
+    }
+    /// Take the projection out of the runner.
+    #[must_use]
+    pub fn into_projection(self) -> P {
+        self.projection
+    }
+    /// Get the store for the checkpoint of the projection.
+    #[must_use]
+    pub fn checkpoint_store(&self) -> &S {
+        &self.checkpoint_store
+    }
+
+    /// Process all events after the checkpoint and keep processing new events as they are written.
+    ///
+    /// The checkpoint is saved after every processed event. Lost connections are re-established according to the retry policy,
+    /// so this only returns if an error occurs.
+    /// An event that fails the signature verification of the client is such an error: the runner stops before it instead of
+    /// skipping it, so the checkpoint still points to the event before and the next run starts with the rejected event again.
+    ///
+    /// # Errors
+    /// Returns an error if the checkpoint can't be read or written, observing the events fails or the projection
+    /// can't process an event.
+    pub async fn run(&mut self) -> Result<(), ProjectionError<P::Error>> {
+        let checkpoint = self.checkpoint_store.load(&self.name).await?;
+        let options = ObserveEventsOptions {
+            lower_bound: checkpoint.as_deref().map(|id| Bound {
+                bound_type: BoundType::Exclusive,
+                id,
+            }),
+            recursive: true,
+            ..Default::default()
+        };
+        let mut events = self
+            .client
+            .observe_events_resilient(&self.subject, Some(options), self.retry_policy.clone())
+            .await?;
+        while let Some(event) = events.next().await {
+            let event = event?;
+            self.projection
+                .apply(&event)
+                .await
+                .map_err(|error| ProjectionError::Projection {
+                    event_id: event.id().to_string(),
+                    error,
+                })?;
+            self.checkpoint_store.save(&self.name, event.id()).await?;
+        }
+        Ok(())
+    }
+}
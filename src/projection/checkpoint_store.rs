@@ -0,0 +1,110 @@
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    future::Future,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::{Mutex, PoisonError},
+};
+
+use crate::{error::ClientError, file_store};
+
+/// Stores the ID of the last event each projection has processed.
+///
+/// Implement this trait to keep checkpoints next to the read model, so both are updated consistently.
+pub trait CheckpointStore: Debug + Send + Sync {
+    /// Load the ID of the last event processed by a projection, or `None` if it has not processed any events yet.
+    ///
+    /// # Errors
+    /// Returns an error if the checkpoint can't be read.
+    fn load(&self, name: &str) -> impl Future<Output = Result<Option<String>, ClientError>> + Send;
+
+    /// Store the ID of the last event processed by a projection, replacing the previous checkpoint.
+    ///
+    /// # Errors
+    /// Returns an error if the checkpoint can't be written.
+    fn save(
+        &self,
+        name: &str,
+        last_event_id: &str,
+    ) -> impl Future<Output = Result<(), ClientError>> + Send;
+}
+
+/// Remembers checkpoints only as long as the store lives, so projections using it start from the first event after a restart.
+#[derive(Debug, Default)]
+pub struct InMemoryCheckpointStore {
+    checkpoints: Mutex<HashMap<String, String>>,
+}
+
+impl InMemoryCheckpointStore {
+    /// Create an empty store.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CheckpointStore for InMemoryCheckpointStore {
+    async fn load(&self, name: &str) -> Result<Option<String>, ClientError> {
+        Ok(self
+            .checkpoints
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(name)
+            .cloned())
+    }
+
+    async fn save(&self, name: &str, last_event_id: &str) -> Result<(), ClientError> {
+        let _ = self
+            .checkpoints
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(name.to_string(), last_event_id.to_string());
+        Ok(())
+    }
+}
+
+/// Writes the checkpoint of each projection to a small text file containing the ID of the last processed event.
+///
+/// Every save is flushed to disk before it replaces the previous checkpoint, so a restarted projection never finds
+/// an empty or partial event ID. The directory doesn't need to exist beforehand.
+#[derive(Debug, Clone)]
+pub struct FileCheckpointStore {
+    directory: PathBuf,
+}
+
+impl FileCheckpointStore {
+    /// Create a store that keeps its files in the given directory.
+    #[must_use]
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    /// Get the directory the store keeps its files in.
+    #[must_use]
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Utility function to get the file of a projection, which is named after a hash of the projection name.
+    fn path(&self, name: &str) -> PathBuf {
+        file_store::key_path(&self.directory, name, "checkpoint")
+    }
+}
+
+impl CheckpointStore for FileCheckpointStore {
+    async fn load(&self, name: &str) -> Result<Option<String>, ClientError> {
+        match tokio::fs::read_to_string(self.path(name)).await {
+            Ok(last_event_id) => Ok(Some(last_event_id.trim().to_string())),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    async fn save(&self, name: &str, last_event_id: &str) -> Result<(), ClientError> {
+        file_store::write_atomically(&self.path(name), last_event_id.as_bytes()).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,198 @@
+//! Subjects are the paths events are written to, e.g. `/books/42`.
+//!
+//! A [`Subject`] is validated when it is created, so malformed subjects are caught where they are built.
+//! The methods of the [`crate::client::Client`] that read or observe events take a `Subject` as well as a plain `&str`,
+//! and validate the subject before sending a request.
+//! ```
+//! use eventsourcingdb::Subject;
+//! let book: Subject = "/books/42".parse().expect("Invalid subject");
+//! let loans = book.join("loans").expect("Invalid subject");
+//! assert_eq!(loans.as_str(), "/books/42/loans");
+//! assert_eq!(loans.parent().as_ref(), Some(&book));
+//! assert!(book.is_ancestor_of(&loans));
+//! assert_eq!(loans.components().collect::<Vec<_>>(), ["books", "42", "loans"]);
+//! assert!("books/42/".parse::<Subject>().is_err());
+//! ```
+//!
+//! To browse the subjects in the DB, build a [`SubjectTree`] with [`crate::client::Client::subject_tree`].
+
+mod subject_tree;
+
+use std::{fmt, ops::Deref, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::SubjectError;
+pub use subject_tree::{SubjectNode, SubjectTree};
+
+/// A validated subject.
+///
+/// A subject starts with a slash and consists of segments separated by slashes.
+/// Segments must not be empty and may only contain ASCII letters, digits, `_` and `-`.
+/// The subject may end with a slash, which doesn't add a segment.
+/// The root subject `/` has no segments.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Subject(String);
+
+impl Subject {
+    /// Create a subject after validating it.
+    ///
+    /// # Errors
+    /// Returns an error if the subject is malformed.
+    pub fn new(subject: impl Into<String>) -> Result<Self, SubjectError> {
+        let subject = subject.into();
+        Self::validate(&subject)?;
+        Ok(Self(subject))
+    }
+
+    /// The root subject `/`, which contains all other subjects.
+    #[must_use]
+    pub fn root() -> Self {
+        Self("/".to_string())
+    }
+
+    /// Check whether a string is a valid subject.
+    ///
+    /// The rules are the ones the DB enforces for subjects, see the [documentation on subjects](https://docs.eventsourcingdb.io/fundamentals/subjects/).
+    ///
+    /// # Errors
+    /// Returns an error if the subject is malformed.
+    pub fn validate(subject: &str) -> Result<(), SubjectError> {
+        // This mirrors the pattern of the DB: ^/([0-9A-Za-z_-]+/)*[0-9A-Za-z_-]*$
+        let invalid = |reason: &str| {
+            Err(SubjectError {
+                subject: subject.to_string(),
+                reason: reason.to_string(),
+            })
+        };
+        let Some(path) = subject.strip_prefix('/') else {
+            return invalid("it must start with '/'");
+        };
+        if path.split('/').rev().skip(1).any(str::is_empty) {
+            return invalid("it must not contain empty segments");
+        }
+        if !path
+            .chars()
+            .all(|character| character == '/' || is_segment_character(character))
+        {
+            return invalid("its segments may only contain ASCII letters, digits, '_' and '-'");
+        }
+        Ok(())
+    }
+
+    /// Get the subject as a string.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether this is the root subject `/`.
+    #[must_use]
+    pub fn is_root(&self) -> bool {
+        self.0 == "/"
+    }
+
+    /// Iterate over the segments of the subject, e.g. `books` and `42` for `/books/42`.
+    pub fn components(&self) -> impl Iterator<Item = &str> {
+        self.0[1..].split('/').filter(|segment| !segment.is_empty())
+    }
+
+    /// Get the subject one level up, or `None` for the root subject.
+    #[must_use]
+    pub fn parent(&self) -> Option<Self> {
+        if self.is_root() {
+            return None;
+        }
+        let path = self.0.trim_end_matches('/');
+        let end = path.rfind('/').unwrap_or_default();
+        Some(Self(path[..end.max(1)].to_string()))
+    }
+
+    /// Append a relative path of one or more segments, e.g. `loans` or `loans/1`.
+    ///
+    /// # Errors
+    /// Returns an error if the path has empty or malformed segments.
+    pub fn join(&self, path: &str) -> Result<Self, SubjectError> {
+        if path.split('/').any(str::is_empty) {
+            return Err(SubjectError {
+                subject: path.to_string(),
+                reason: "the path must not contain empty segments".to_string(),
+            });
+        }
+        if self.0.ends_with('/') {
+            Self::new(format!("{}{path}", self.0))
+        } else {
+            Self::new(format!("{}/{path}", self.0))
+        }
+    }
+
+    /// Whether this subject contains the other subject at any level below it.
+    ///
+    /// A subject is not its own ancestor.
+    #[must_use]
+    pub fn is_ancestor_of(&self, other: &Subject) -> bool {
+        if self.is_root() {
+            return !other.is_root();
+        }
+        other
+            .0
+            .strip_prefix(self.0.trim_end_matches('/'))
+            .is_some_and(|rest| rest.starts_with('/') && rest != "/")
+    }
+}
+
+/// Utility function to check whether a character may be part of a segment of a subject.
+fn is_segment_character(character: char) -> bool {
+    character.is_ascii_alphanumeric() || character == '_' || character == '-'
+}
+
+impl Deref for Subject {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Subject {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Subject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for Subject {
+    type Err = SubjectError;
+
+    fn from_str(subject: &str) -> Result<Self, Self::Err> {
+        Self::new(subject)
+    }
+}
+
+impl TryFrom<String> for Subject {
+    type Error = SubjectError;
+
+    fn try_from(subject: String) -> Result<Self, Self::Error> {
+        Self::new(subject)
+    }
+}
+
+impl TryFrom<&str> for Subject {
+    type Error = SubjectError;
+
+    fn try_from(subject: &str) -> Result<Self, Self::Error> {
+        Self::new(subject)
+    }
+}
+
+impl From<Subject> for String {
+    fn from(subject: Subject) -> Self {
+        subject.0
+    }
+}
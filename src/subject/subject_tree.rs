@@ -0,0 +1,233 @@
+use std::collections::BTreeMap;
+
+use crate::{error::SubjectError, subject::Subject};
+
+/// An in-memory tree of subjects, see [`crate::client::Client::subject_tree`].
+///
+/// Subjects that only exist as ancestors of other subjects are part of the tree as well,
+/// [`SubjectNode::has_events`] tells them apart from the subjects returned by the DB.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubjectTree {
+    root: SubjectNode,
+}
+
+/// A subject within a [`SubjectTree`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubjectNode {
+    subject: Subject,
+    has_events: bool,
+    children: BTreeMap<String, SubjectNode>,
+}
+
+impl SubjectTree {
+    /// Build a tree below the base subject.
+    ///
+    /// Subjects that are neither the base subject nor below it are ignored.
+    #[must_use]
+    pub fn new(base: Subject, subjects: impl IntoIterator<Item = Subject>) -> Self {
+        let mut root = SubjectNode::new(base);
+        for subject in subjects {
+            if subject == root.subject {
+                root.has_events = true;
+            } else if root.subject.is_ancestor_of(&subject) {
+                let components: Vec<&str> = subject.components().collect();
+                let depth = root.subject.components().count();
+                root.insert(&components[depth..]);
+            }
+        }
+        Self { root }
+    }
+
+    /// Get the node of the base subject.
+    #[must_use]
+    pub fn root(&self) -> &SubjectNode {
+        &self.root
+    }
+
+    /// Get the node of a subject, if it is part of the tree.
+    #[must_use]
+    pub fn get(&self, subject: &Subject) -> Option<&SubjectNode> {
+        if *subject == self.root.subject {
+            return Some(&self.root);
+        }
+        if !self.root.subject.is_ancestor_of(subject) {
+            return None;
+        }
+        let depth = self.root.subject.components().count();
+        subject
+            .components()
+            .skip(depth)
+            .try_fold(&self.root, |node, name| node.child(name))
+    }
+
+    /// List the subjects below the base subject depth-first, in alphabetical order on each level.
+    ///
+    /// With a maximum depth, only the subjects up to that many levels below the base subject are listed,
+    /// e.g. only the direct children for a depth of 1.
+    #[must_use]
+    pub fn descendants(&self, max_depth: Option<usize>) -> Vec<&SubjectNode> {
+        let mut nodes = Vec::new();
+        self.root.collect(max_depth, &mut nodes);
+        nodes
+    }
+
+    /// List the subjects that match a glob pattern, including the base subject.
+    ///
+    /// Within a segment, `*` matches any characters, e.g. `/books/*/loans` or `/users/j*`.
+    /// A segment of `**` matches any number of segments, e.g. `/books/**` matches `/books` and all subjects below it.
+    ///
+    /// # Errors
+    /// Returns an error if the pattern is not a valid subject apart from its wildcards.
+    pub fn matching(&self, pattern: &str) -> Result<Vec<&SubjectNode>, SubjectError> {
+        Subject::validate(&pattern.replace('*', "_")).map_err(|error| SubjectError {
+            subject: pattern.to_string(),
+            ..error
+        })?;
+        let pattern: Vec<&str> = pattern[1..]
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect();
+        let mut nodes = vec![&self.root];
+        self.root.collect(None, &mut nodes);
+        nodes.retain(|node| {
+            let components: Vec<&str> = node.subject.components().collect();
+            matches_path(&pattern, &components)
+        });
+        Ok(nodes)
+    }
+}
+
+impl SubjectNode {
+    fn new(subject: Subject) -> Self {
+        Self {
+            subject,
+            has_events: false,
+            children: BTreeMap::new(),
+        }
+    }
+
+    /// Utility function to add a subject given by its components relative to this node.
+    fn insert(&mut self, components: &[&str]) {
+        let Some((name, rest)) = components.split_first() else {
+            self.has_events = true;
+            return;
+        };
+        let child = self.children.entry((*name).to_string()).or_insert_with(|| {
+            let subject = self
+                .subject
+                .join(name)
+                .expect("components of a valid subject form a valid subject");
+            Self::new(subject)
+        });
+        child.insert(rest);
+    }
+
+    /// Utility function to collect the nodes below this node depth-first.
+    fn collect<'a>(&'a self, max_depth: Option<usize>, nodes: &mut Vec<&'a SubjectNode>) {
+        if max_depth == Some(0) {
+            return;
+        }
+        for child in self.children.values() {
+            nodes.push(child);
+            child.collect(max_depth.map(|depth| depth - 1), nodes);
+        }
+    }
+
+    /// Get the subject of the node.
+    #[must_use]
+    pub fn subject(&self) -> &Subject {
+        &self.subject
+    }
+
+    /// Get the last segment of the subject, or `None` for the root subject.
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        self.subject.components().last()
+    }
+
+    /// Whether the DB returned this subject, as opposed to it only being an ancestor of other subjects.
+    #[must_use]
+    pub fn has_events(&self) -> bool {
+        self.has_events
+    }
+
+    /// Iterate over the direct children of the node in alphabetical order.
+    pub fn children(&self) -> impl Iterator<Item = &SubjectNode> {
+        self.children.values()
+    }
+
+    /// Get a direct child by the last segment of its subject.
+    #[must_use]
+    pub fn child(&self, name: &str) -> Option<&SubjectNode> {
+        self.children.get(name)
+    }
+
+    /// Get the number of direct children.
+    #[must_use]
+    pub fn child_count(&self) -> usize {
+        self.children.len()
+    }
+
+    /// Get the number of subjects at any level below this node.
+    #[must_use]
+    pub fn descendant_count(&self) -> usize {
+        self.children
+            .values()
+            .map(|child| 1 + child.descendant_count())
+            .sum()
+    }
+}
+
+/// Utility function to match the components of a subject against the segments of a glob pattern.
+///
+/// When a segment does not match, this only backtracks to the most recent `**`,
+/// so the effort grows with the product of pattern and subject length instead of exponentially.
+fn matches_path(pattern: &[&str], components: &[&str]) -> bool {
+    let (mut pattern_index, mut component_index) = (0, 0);
+    let mut backtrack = None;
+    while component_index < components.len() {
+        match pattern.get(pattern_index) {
+            Some(&"**") => {
+                backtrack = Some((pattern_index, component_index));
+                pattern_index += 1;
+            }
+            Some(segment) if matches_segment(segment, components[component_index]) => {
+                pattern_index += 1;
+                component_index += 1;
+            }
+            _ => {
+                let Some((wildcard_index, skipped_index)) = backtrack else {
+                    return false;
+                };
+                backtrack = Some((wildcard_index, skipped_index + 1));
+                pattern_index = wildcard_index + 1;
+                component_index = skipped_index + 1;
+            }
+        }
+    }
+    pattern[pattern_index..]
+        .iter()
+        .all(|segment| *segment == "**")
+}
+
+/// Utility function to match a single component against a pattern segment in which `*` matches any characters.
+fn matches_segment(pattern: &str, component: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let [first, middle @ .., last] = parts.as_slice() else {
+        return pattern == component;
+    };
+    if component.len() < first.len() + last.len()
+        || !component.starts_with(first)
+        || !component.ends_with(last)
+    {
+        return false;
+    }
+    let mut rest = &component[first.len()..component.len() - last.len()];
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
@@ -0,0 +1,354 @@
+mod utils;
+
+use eventsourcingdb::{
+    Client,
+    aggregate::{
+        Aggregate, FileSnapshotStore, InMemorySnapshotStore, Repository, Snapshot,
+        SnapshotRepository, SnapshotStore,
+    },
+    error::{CommandError, EventError},
+    event::{Event, EventCandidate},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio::runtime::Handle;
+use utils::{create_test_container, create_test_eventcandidate};
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Counter {
+    value: u32,
+}
+
+#[derive(Deserialize)]
+struct Increased {
+    value: u32,
+}
+
+#[derive(Debug, PartialEq)]
+struct LimitReached;
+
+impl Aggregate for Counter {
+    type Command = u32;
+    type Error = LimitReached;
+
+    fn apply(&mut self, event: &Event) -> Result<(), EventError> {
+        let increased: Increased = event.data_as()?;
+        self.value += increased.value;
+        Ok(())
+    }
+
+    fn handle(&self, subject: &str, by: u32) -> Result<Vec<EventCandidate>, LimitReached> {
+        if self.value + by > 10 {
+            return Err(LimitReached);
+        }
+        Ok(vec![create_test_eventcandidate(
+            subject,
+            json!({"value": by}),
+        )])
+    }
+}
+
+#[tokio::test]
+async fn load_aggregate_without_events() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let repository = Repository::<Counter>::new(client);
+    let counter = repository
+        .load("/counter")
+        .await
+        .expect("Failed to load aggregate");
+    assert_eq!(counter.subject(), "/counter");
+    assert_eq!(counter.state(), &Counter::default());
+    assert_eq!(counter.last_event_id(), None);
+}
+
+#[tokio::test]
+async fn load_aggregate_replays_events() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let written = client
+        .write_events(
+            vec![
+                create_test_eventcandidate("/counter", json!({"value": 1})),
+                create_test_eventcandidate("/counter", json!({"value": 2})),
+                create_test_eventcandidate("/counter/nested", json!({"value": 4})),
+            ],
+            vec![],
+        )
+        .await
+        .expect("Failed to write events");
+    let repository = Repository::<Counter>::new(client);
+    let counter = repository
+        .load("/counter")
+        .await
+        .expect("Failed to load aggregate");
+    assert_eq!(counter.state().value, 3);
+    assert_eq!(counter.last_event_id(), Some(written[1].id()));
+}
+
+#[tokio::test]
+async fn commit_aggregate_events() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let repository = Repository::<Counter>::new(client);
+    let mut counter = repository
+        .load("/counter")
+        .await
+        .expect("Failed to load aggregate");
+
+    let events = counter.handle(3).expect("Failed to handle command");
+    let written = repository
+        .commit(&mut counter, events)
+        .await
+        .expect("Failed to commit events");
+    assert_eq!(written.len(), 1);
+    assert_eq!(counter.state().value, 3);
+    assert_eq!(counter.last_event_id(), Some(written[0].id()));
+
+    let events = counter.handle(4).expect("Failed to handle command");
+    let _ = repository
+        .commit(&mut counter, events)
+        .await
+        .expect("Failed to commit events");
+    assert_eq!(counter.state().value, 7);
+
+    assert_eq!(counter.handle(4), Err(LimitReached));
+
+    let reloaded = repository
+        .load("/counter")
+        .await
+        .expect("Failed to load aggregate");
+    assert_eq!(reloaded, counter);
+}
+
+#[tokio::test]
+async fn commit_aggregate_events_after_concurrent_write_fails() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let repository = Repository::<Counter>::new(client);
+    let mut first = repository
+        .load("/counter")
+        .await
+        .expect("Failed to load aggregate");
+    let mut second = repository
+        .load("/counter")
+        .await
+        .expect("Failed to load aggregate");
+
+    let events = first.handle(1).expect("Failed to handle command");
+    let _ = repository
+        .commit(&mut first, events)
+        .await
+        .expect("Failed to commit events");
+
+    let events = second.handle(2).expect("Failed to handle command");
+    let error = repository
+        .commit(&mut second, events)
+        .await
+        .expect_err("Expected the commit to fail");
+    assert!(
+        error.is_precondition_failed(),
+        "Expected a failed precondition, but got: {error:?}"
+    );
+    assert_eq!(second.state().value, 0);
+
+    let second = repository
+        .catch_up(second)
+        .await
+        .expect("Failed to catch up aggregate");
+    assert_eq!(second, first);
+}
+
+/// Writes an event to the counter from within a decision, as if another writer got there first.
+fn write_concurrently(client: &Client, value: u32) {
+    let _ = tokio::task::block_in_place(|| {
+        Handle::current().block_on(client.write_events(
+            vec![create_test_eventcandidate(
+                "/counter",
+                json!({"value": value}),
+            )],
+            vec![],
+        ))
+    })
+    .expect("Failed to write events");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn execute_command_retries_after_concurrent_write() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let repository = Repository::<Counter>::new(client.clone());
+
+    let mut attempts = 0;
+    let written = repository
+        .execute_command("/counter", |counter| {
+            attempts += 1;
+            if attempts == 1 {
+                write_concurrently(&client, 5);
+            }
+            counter.handle(2)
+        })
+        .await
+        .expect("Failed to execute command");
+    assert_eq!(attempts, 2);
+    assert_eq!(written.len(), 1);
+
+    let counter = repository
+        .load("/counter")
+        .await
+        .expect("Failed to load aggregate");
+    assert_eq!(counter.state().value, 7);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn execute_command_fails_after_max_conflict_retries() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let repository = Repository::<Counter>::new(client.clone()).with_max_conflict_retries(1);
+
+    let error = repository
+        .execute_command("/counter", |counter| {
+            write_concurrently(&client, 1);
+            counter.handle(1)
+        })
+        .await
+        .expect_err("Expected the command to fail");
+    assert!(
+        matches!(error, CommandError::Conflict { attempts: 2, .. }),
+        "Expected a conflict, but got: {error:?}"
+    );
+}
+
+#[tokio::test]
+async fn execute_command_returns_rejection() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let repository = Repository::<Counter>::new(client);
+
+    let error = repository
+        .execute_command("/counter", |counter| counter.handle(11))
+        .await
+        .expect_err("Expected the command to be rejected");
+    assert!(
+        matches!(error, CommandError::Rejected(LimitReached)),
+        "Expected a rejection, but got: {error:?}"
+    );
+}
+
+#[tokio::test]
+async fn load_aggregate_from_snapshot() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let written = client
+        .write_events(
+            vec![
+                create_test_eventcandidate("/counter", json!({"value": 1})),
+                create_test_eventcandidate("/counter", json!({"value": 2})),
+            ],
+            vec![],
+        )
+        .await
+        .expect("Failed to write events");
+    let store = InMemorySnapshotStore::new();
+    store
+        .save(&Snapshot {
+            subject: "/counter".to_string(),
+            last_event_id: written[0].id().to_string(),
+            state: json!({"value": 5}),
+        })
+        .await
+        .expect("Failed to save snapshot");
+    let repository = SnapshotRepository::new(Repository::<Counter>::new(client), store);
+
+    let counter = repository
+        .load("/counter")
+        .await
+        .expect("Failed to load aggregate");
+    assert_eq!(counter.state().value, 7);
+    assert_eq!(counter.last_event_id(), Some(written[1].id()));
+    assert_eq!(counter.events_since_snapshot(), 1);
+}
+
+#[tokio::test]
+async fn commit_aggregate_events_saves_snapshots() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let repository = SnapshotRepository::new(
+        Repository::<Counter>::new(client),
+        InMemorySnapshotStore::new(),
+    )
+    .with_snapshot_frequency(2);
+    let mut counter = repository
+        .load("/counter")
+        .await
+        .expect("Failed to load aggregate");
+
+    for by in [1, 2] {
+        let events = counter.handle(by).expect("Failed to handle command");
+        let _ = repository
+            .commit(&mut counter, events)
+            .await
+            .expect("Failed to commit events");
+    }
+    assert_eq!(counter.events_since_snapshot(), 0);
+    let snapshot = repository
+        .store()
+        .load("/counter")
+        .await
+        .expect("Failed to load snapshot")
+        .expect("Expected a snapshot");
+    assert_eq!(snapshot.state, json!({"value": 3}));
+    assert_eq!(
+        Some(snapshot.last_event_id.as_str()),
+        counter.last_event_id()
+    );
+}
+
+#[tokio::test]
+async fn file_snapshot_store_round_trip() {
+    let directory = std::env::temp_dir().join(format!("esdb-snapshots-{}", std::process::id()));
+    let store = FileSnapshotStore::new(&directory);
+    assert_eq!(
+        store
+            .load("/counter")
+            .await
+            .expect("Failed to load snapshot"),
+        None
+    );
+
+    let snapshot = Snapshot {
+        subject: "/counter".to_string(),
+        last_event_id: "3".to_string(),
+        state: json!({"value": 5}),
+    };
+    store
+        .save(&snapshot)
+        .await
+        .expect("Failed to save snapshot");
+    assert_eq!(
+        store
+            .load("/counter")
+            .await
+            .expect("Failed to load snapshot"),
+        Some(snapshot)
+    );
+
+    let long_subject = format!("/counters/{}", "a".repeat(300));
+    let snapshot = Snapshot {
+        subject: long_subject.clone(),
+        last_event_id: "4".to_string(),
+        state: json!({"value": 6}),
+    };
+    store
+        .save(&snapshot)
+        .await
+        .expect("Failed to save snapshot with a long subject");
+    assert_eq!(
+        store
+            .load(&long_subject)
+            .await
+            .expect("Failed to load snapshot"),
+        Some(snapshot)
+    );
+    std::fs::remove_dir_all(directory).expect("Failed to remove snapshot directory");
+}
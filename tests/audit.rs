@@ -0,0 +1,156 @@
+mod utils;
+
+use eventsourcingdb::{
+    audit::{AuditFailureKind, AuditReport, audit},
+    client::Client,
+    container::Container,
+    event::SignatureVerification,
+};
+use serde_json::json;
+use utils::{create_test_container, create_test_eventcandidate};
+
+#[tokio::test]
+async fn audit_empty_event_store() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+
+    let report = audit(&client, None).await.expect("Failed to audit events");
+
+    assert!(report.is_valid());
+    assert_eq!(report.event_count, 0);
+    assert_eq!(report.first_event_id, None);
+}
+
+#[tokio::test]
+async fn audit_event_store() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let written = client
+        .write_events(
+            vec![
+                create_test_eventcandidate("/books/1", json!({"value": 1})),
+                create_test_eventcandidate("/authors/1", json!({"value": 2})),
+                create_test_eventcandidate("/books/2", json!({"value": 3})),
+            ],
+            vec![],
+        )
+        .await
+        .expect("Unable to write events");
+
+    let report = audit(&client, None).await.expect("Failed to audit events");
+
+    assert!(
+        report.is_valid(),
+        "Unexpected failures: {:?}",
+        report.failures
+    );
+    assert_eq!(report.event_count, 3);
+    assert_eq!(report.signed_event_count, 0);
+    assert_eq!(report.first_event_id.as_deref(), Some(written[0].id()));
+    assert_eq!(report.last_event_id.as_deref(), Some(written[2].id()));
+    assert_eq!(report.first_event_time.as_ref(), Some(written[0].time()));
+    assert_eq!(report.last_event_time.as_ref(), Some(written[2].time()));
+
+    let serialized = serde_json::to_string(&report).expect("Failed to serialize report");
+    let deserialized: AuditReport =
+        serde_json::from_str(&serialized).expect("Failed to deserialize report");
+    assert_eq!(deserialized, report);
+}
+
+#[tokio::test]
+async fn audit_event_store_with_signatures() {
+    let container = Container::builder()
+        .with_image_tag("preview")
+        .with_signing_key()
+        .start()
+        .await
+        .expect("Failed to start test container");
+    let client = container.get_client().await.unwrap();
+    let _ = client
+        .write_events(
+            vec![
+                create_test_eventcandidate("/books/1", json!({"value": 1})),
+                create_test_eventcandidate("/books/2", json!({"value": 2})),
+            ],
+            vec![],
+        )
+        .await
+        .expect("Unable to write events");
+
+    let report = audit(&client, container.get_verifying_key())
+        .await
+        .expect("Failed to audit events");
+
+    assert!(
+        report.is_valid(),
+        "Unexpected failures: {:?}",
+        report.failures
+    );
+    assert!(report.signatures_verified);
+    assert_eq!(report.signed_event_count, 2);
+}
+
+#[tokio::test]
+async fn audit_unsigned_events_with_verifying_key() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let written = client
+        .write_events(
+            vec![create_test_eventcandidate("/books/1", json!({"value": 1}))],
+            vec![],
+        )
+        .await
+        .expect("Unable to write events");
+
+    let report = audit(&client, Some(&Default::default()))
+        .await
+        .expect("Failed to audit events");
+
+    assert!(!report.is_valid());
+    assert_eq!(report.failures.len(), 1);
+    assert_eq!(report.failures[0].event_id, written[0].id());
+    assert_eq!(report.failures[0].kind, AuditFailureKind::MissingSignature);
+}
+
+#[tokio::test]
+async fn audit_unsigned_events_with_client_signature_verification() {
+    let container = create_test_container().await;
+    let written = container
+        .get_client()
+        .await
+        .unwrap()
+        .write_events(
+            vec![
+                create_test_eventcandidate("/books/1", json!({"value": 1})),
+                create_test_eventcandidate("/books/2", json!({"value": 2})),
+            ],
+            vec![],
+        )
+        .await
+        .expect("Unable to write events");
+    let client = Client::builder()
+        .base_url(container.get_base_url().await.unwrap())
+        .api_token(container.get_api_token())
+        .signature_verification(
+            SignatureVerification::builder()
+                .verifying_key(Default::default())
+                .build(),
+        )
+        .build();
+
+    let report = audit(&client, None).await.expect("Failed to audit events");
+
+    assert!(report.signatures_verified);
+    assert_eq!(report.event_count, 2);
+    assert_eq!(
+        report
+            .failures
+            .iter()
+            .map(|failure| (failure.event_id.as_str(), failure.kind))
+            .collect::<Vec<_>>(),
+        vec![
+            (written[0].id(), AuditFailureKind::MissingSignature),
+            (written[1].id(), AuditFailureKind::MissingSignature),
+        ]
+    );
+}
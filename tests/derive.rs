@@ -0,0 +1,256 @@
+#![cfg(feature = "derive")]
+mod utils;
+
+use std::collections::HashMap;
+
+use eventsourcingdb::{
+    error::EventError,
+    event::{Event, EventCandidate, EventSourcingDbEvent},
+};
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use utils::create_test_container;
+
+#[derive(Debug, PartialEq, EventSourcingDbEvent, Serialize, Deserialize)]
+#[esdb(
+    type = "io.eventsourcingdb.test.book-acquired",
+    subject = "/books/{id}",
+    source = "https://www.eventsourcingdb.io"
+)]
+struct BookAcquired {
+    id: u32,
+    title: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct BookReturned {
+    id: u32,
+    condition: String,
+}
+
+#[derive(Debug, PartialEq, EventSourcingDbEvent)]
+#[esdb(subject = "/books/{id}", source = "https://www.eventsourcingdb.io")]
+enum BookEvent {
+    #[esdb(type = "io.eventsourcingdb.test.book-borrowed")]
+    Borrowed { id: u32, borrower: String },
+    #[esdb(type = "io.eventsourcingdb.test.book-returned")]
+    Returned(BookReturned),
+    #[esdb(type = "io.eventsourcingdb.test.library-closed", subject = "/library")]
+    LibraryClosed,
+}
+
+#[derive(Debug, PartialEq, EventSourcingDbEvent, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", rename_all_fields = "camelCase")]
+#[esdb(
+    subject = "/books/{book_id}",
+    source = "https://www.eventsourcingdb.io"
+)]
+enum RenamedBookEvent {
+    #[esdb(type = "io.eventsourcingdb.test.book-borrowed")]
+    Borrowed { book_id: u32, borrower_name: String },
+    #[serde(rename = "returned", rename_all = "SCREAMING_SNAKE_CASE")]
+    #[esdb(type = "io.eventsourcingdb.test.book-returned")]
+    Returned {
+        book_id: u32,
+        book_condition: String,
+    },
+}
+
+#[derive(Debug, PartialEq, EventSourcingDbEvent, Serialize, Deserialize)]
+#[esdb(
+    type = "io.eventsourcingdb.test.shelf-arranged",
+    subject = "/shelves/{id}",
+    source = "https://www.eventsourcingdb.io"
+)]
+struct ShelfArranged {
+    id: u32,
+    positions: HashMap<(u32, u32), String>,
+}
+
+#[derive(Debug, PartialEq, EventSourcingDbEvent, Serialize, Deserialize)]
+#[esdb(
+    type = "io.eventsourcingdb.test.value-recorded",
+    subject = "/values/{id}",
+    source = "https://www.eventsourcingdb.io"
+)]
+struct ValueRecorded<T> {
+    id: u32,
+    value: T,
+}
+
+#[test]
+fn derive_struct_event_candidate() {
+    let candidate = EventCandidate::try_from(BookAcquired {
+        id: 42,
+        title: "2001 - A Space Odyssey".to_string(),
+    })
+    .expect("Failed to create event candidate");
+    assert_eq!(
+        BookAcquired::EVENT_TYPE,
+        "io.eventsourcingdb.test.book-acquired"
+    );
+    assert_eq!(BookAcquired::SUBJECT_TEMPLATE, "/books/{id}");
+    assert_eq!(candidate.ty, BookAcquired::EVENT_TYPE);
+    assert_eq!(candidate.subject, "/books/42");
+    assert_eq!(candidate.source, "https://www.eventsourcingdb.io");
+    assert_eq!(
+        candidate.data,
+        serde_json::json!({"id": 42, "title": "2001 - A Space Odyssey"})
+    );
+}
+
+#[test]
+fn derive_generic_struct_event_candidate() {
+    let candidate = EventCandidate::try_from(ValueRecorded {
+        id: 42,
+        value: vec!["a".to_string(), "b".to_string()],
+    })
+    .expect("Failed to create event candidate");
+    assert_eq!(candidate.ty, "io.eventsourcingdb.test.value-recorded");
+    assert_eq!(candidate.subject, "/values/42");
+    assert_eq!(
+        candidate.data,
+        serde_json::json!({"id": 42, "value": ["a", "b"]})
+    );
+    assert_eq!(
+        ValueRecorded::<bool>::EVENT_TYPES,
+        ["io.eventsourcingdb.test.value-recorded"]
+    );
+}
+
+#[test]
+fn derive_enum_event_candidates() {
+    let borrowed = BookEvent::Borrowed {
+        id: 42,
+        borrower: "Jane Doe".to_string(),
+    }
+    .to_event_candidate()
+    .expect("Failed to create event candidate");
+    assert_eq!(borrowed.ty, "io.eventsourcingdb.test.book-borrowed");
+    assert_eq!(borrowed.subject, "/books/42");
+    assert_eq!(
+        borrowed.data,
+        serde_json::json!({"id": 42, "borrower": "Jane Doe"})
+    );
+
+    let returned = BookEvent::Returned(BookReturned {
+        id: 23,
+        condition: "good".to_string(),
+    })
+    .to_event_candidate()
+    .expect("Failed to create event candidate");
+    assert_eq!(returned.ty, "io.eventsourcingdb.test.book-returned");
+    assert_eq!(returned.subject, "/books/23");
+    assert_eq!(
+        returned.data,
+        serde_json::json!({"id": 23, "condition": "good"})
+    );
+
+    let closed = BookEvent::LibraryClosed
+        .to_event_candidate()
+        .expect("Failed to create event candidate");
+    assert_eq!(closed.ty, "io.eventsourcingdb.test.library-closed");
+    assert_eq!(closed.subject, "/library");
+    assert_eq!(closed.data, serde_json::json!({}));
+
+    assert_eq!(
+        BookEvent::EVENT_TYPES,
+        &[
+            "io.eventsourcingdb.test.book-borrowed",
+            "io.eventsourcingdb.test.book-returned",
+            "io.eventsourcingdb.test.library-closed",
+        ]
+    );
+}
+
+#[test]
+fn derive_enum_honours_serde_attributes() {
+    let borrowed = EventCandidate::try_from(RenamedBookEvent::Borrowed {
+        book_id: 42,
+        borrower_name: "Jane Doe".to_string(),
+    })
+    .expect("Failed to create event candidate");
+    assert_eq!(borrowed.subject, "/books/42");
+    assert_eq!(
+        borrowed.data,
+        serde_json::json!({"bookId": 42, "borrowerName": "Jane Doe"})
+    );
+
+    let returned = RenamedBookEvent::Returned {
+        book_id: 42,
+        book_condition: "good".to_string(),
+    }
+    .to_event_candidate()
+    .expect("Failed to create event candidate");
+    assert_eq!(
+        returned.data,
+        serde_json::json!({"BOOK_ID": 42, "BOOK_CONDITION": "good"})
+    );
+}
+
+#[test]
+fn derive_event_candidate_fails_for_unserializable_data() {
+    let result = EventCandidate::try_from(ShelfArranged {
+        id: 1,
+        positions: HashMap::from([((1, 2), "2001 - A Space Odyssey".to_string())]),
+    });
+    assert!(
+        result.is_err(),
+        "Expected a serialization error, got {result:?}"
+    );
+}
+
+#[tokio::test]
+async fn derive_events_round_trip() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let acquired = BookAcquired {
+        id: 42,
+        title: "2001 - A Space Odyssey".to_string(),
+    };
+    let borrowed = BookEvent::Borrowed {
+        id: 42,
+        borrower: "Jane Doe".to_string(),
+    };
+    let returned = BookEvent::Returned(BookReturned {
+        id: 42,
+        condition: "good".to_string(),
+    });
+    let candidates = vec![
+        acquired.to_event_candidate().unwrap(),
+        borrowed.to_event_candidate().unwrap(),
+        returned.to_event_candidate().unwrap(),
+    ];
+    client
+        .write_events(candidates, vec![])
+        .await
+        .expect("Failed to write events");
+
+    let events: Vec<Event> = client
+        .read_events("/books/42", None)
+        .await
+        .expect("Failed to read events")
+        .try_collect()
+        .await
+        .expect("Failed to read events");
+    assert_eq!(events.len(), 3);
+
+    assert_eq!(
+        BookAcquired::try_from(&events[0]).expect("Failed to convert event"),
+        acquired
+    );
+    assert_eq!(
+        BookEvent::from_event(&events[1]).expect("Failed to convert event"),
+        borrowed
+    );
+    assert_eq!(
+        BookEvent::from_event(&events[2]).expect("Failed to convert event"),
+        returned
+    );
+
+    let result = BookEvent::from_event(&events[0]);
+    assert!(
+        matches!(result, Err(EventError::UnexpectedEventType(ref ty)) if ty == BookAcquired::EVENT_TYPE),
+        "Expected an unexpected event type error, got {result:?}"
+    );
+}
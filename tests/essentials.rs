@@ -1,6 +1,9 @@
 mod utils;
-use eventsourcingdb::Client;
-use utils::create_test_container;
+use std::time::{Duration, Instant};
+
+use eventsourcingdb::{Client, client::RetryPolicy, error::ClientError};
+use serde_json::json;
+use utils::{create_test_container, create_test_eventcandidate};
 
 #[tokio::test]
 async fn ping() {
@@ -41,3 +44,85 @@ async fn verify_api_token_invalid_token_errors() {
     let result = invalid_client.verify_api_token().await;
     assert!(result.is_err(), "Expected an error, but got: {result:?}");
 }
+
+#[tokio::test]
+async fn read_events_invalid_token_is_unauthorized() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let invalid_client = Client::new(client.get_base_url().clone(), "invalid_token");
+    let error = invalid_client
+        .read_events("/", None)
+        .await
+        .err()
+        .expect("Expected an error");
+    assert!(
+        error.is_unauthorized(),
+        "Expected an unauthorized error, but got: {error:?}"
+    );
+}
+
+#[tokio::test]
+async fn ping_with_custom_http_client() {
+    let container = create_test_container().await;
+    let http_client = reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(5))
+        .user_agent("eventsourcingdb-tests")
+        .build()
+        .expect("Failed to build HTTP client");
+    let client = Client::builder()
+        .base_url(container.get_base_url().await.unwrap())
+        .api_token(container.get_api_token())
+        .reqwest(http_client)
+        .timeout(Duration::from_secs(10))
+        .build();
+    client.ping().await.expect("Failed to ping");
+}
+
+#[tokio::test]
+async fn ping_exceeding_timeout_errors() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let result = client.with_timeout(Duration::from_nanos(1)).ping().await;
+    assert!(
+        matches!(result, Err(ClientError::RequestTimeout(_))),
+        "Expected a timeout error, but got: {result:?}"
+    );
+}
+
+#[tokio::test]
+async fn ping_unavailable_server_retries_with_backoff() {
+    let retry_policy = RetryPolicy::builder()
+        .max_attempts(3)
+        .initial_backoff(Duration::from_millis(50))
+        .jitter(false)
+        .build();
+    let client = Client::builder()
+        .base_url("http://localhost:12345".parse().unwrap())
+        .api_token("secrettoken")
+        .retry_policy(retry_policy)
+        .build();
+    let started = Instant::now();
+    let result = client.ping().await;
+    assert!(result.is_err(), "Expected an error, but got: {result:?}");
+    assert!(
+        started.elapsed() >= Duration::from_millis(150),
+        "Expected two retries with backoff, but only waited {:?}",
+        started.elapsed()
+    );
+}
+
+#[tokio::test]
+async fn write_events_with_retry_policy() {
+    let container = create_test_container().await;
+    let client = Client::builder()
+        .base_url(container.get_base_url().await.unwrap())
+        .api_token(container.get_api_token())
+        .retry_policy(RetryPolicy::default())
+        .build();
+    let event_candidate = create_test_eventcandidate("/test", json!({"value": 1}));
+    let written = client
+        .write_events(vec![event_candidate], vec![])
+        .await
+        .expect("Failed to write events");
+    assert_eq!(written.len(), 1, "Expected exactly one written event");
+}
@@ -0,0 +1,188 @@
+use eventsourcingdb::{
+    error::EventQlError,
+    eventql::{self, EventQl, Expr, Order},
+};
+
+#[test]
+fn check_valid_queries() {
+    let queries = [
+        "FROM e IN events PROJECT INTO e",
+        "FROM e IN events ORDER BY e.time DESC TOP 100 PROJECT INTO e",
+        "FROM e IN events PROJECT INTO COUNT() == 0",
+        "from e in events where e.data.order >= 1.5e3 project into e",
+        "FROM e IN events\nWHERE e.type == 'io.eventsourcingdb.test' AND NOT (e.data.value < -1 OR e.data.value % 2 != 0)\nPROJECT INTO { id: e.id, \"value\": e.data.values[0] * 2 }",
+        "FROM e IN events GROUP BY e.type HAVING COUNT() > 1 PROJECT INTO DISTINCT [e.type, COUNT()]",
+        "FROM r IN (FROM e IN events SKIP 10 TOP 5 PROJECT INTO { subject: e.subject }) PROJECT INTO r.subject",
+    ];
+    for query in queries {
+        let result = eventql::check(query);
+        assert!(
+            result.is_ok(),
+            "Expected {query:?} to be valid, but got: {result:?}"
+        );
+    }
+}
+
+#[test]
+fn check_built_query() {
+    let query = EventQl::from("e")
+        .unwrap()
+        .in_events()
+        .where_(
+            Expr::field("e.data.title")
+                .unwrap()
+                .equals("\"quoted\"\n")
+                .or(!Expr::field("e.data.lent").unwrap()),
+        )
+        .where_(Expr::field("e.data.year").unwrap().greater_than(1999))
+        .group_by(Expr::field("e.data.author").unwrap())
+        .order_by(Expr::field("e.data.author").unwrap(), Order::Ascending)
+        .skip(5)
+        .top(10)
+        .project_into(
+            Expr::object([
+                ("author", Expr::field("e.data.author").unwrap()),
+                ("count", Expr::function("COUNT", []).unwrap()),
+                (
+                    "tags",
+                    Expr::array([Expr::from("a"), Expr::null(), Expr::try_from(1.5).unwrap()]),
+                ),
+            ])
+            .unwrap(),
+        );
+    let outer = EventQl::from("row")
+        .unwrap()
+        .in_query(&query)
+        .project_into(Expr::field("row.author").unwrap());
+    assert_eq!(eventql::check(query.as_str()), Ok(()));
+    assert_eq!(eventql::check(outer.as_str()), Ok(()));
+}
+
+#[test]
+fn build_query_with_invalid_identifiers() {
+    let invalid = |name: &str| EventQlError::InvalidIdentifier {
+        name: name.to_string(),
+    };
+    assert_eq!(EventQl::from("book-id").unwrap_err(), invalid("book-id"));
+    assert_eq!(EventQl::from("").unwrap_err(), invalid(""));
+    assert_eq!(EventQl::from("WHERE").unwrap_err(), invalid("WHERE"));
+    assert_eq!(Expr::field("1e.data").unwrap_err(), invalid("1e"));
+    assert_eq!(Expr::field(".data").unwrap_err(), invalid(""));
+    assert_eq!(
+        Expr::field("e.data.book-id").unwrap_err(),
+        invalid("book-id")
+    );
+    assert_eq!(Expr::field("e.data.order").unwrap_err(), invalid("order"));
+    assert_eq!(
+        Expr::function("COUNT()", []).unwrap_err(),
+        invalid("COUNT()")
+    );
+    assert_eq!(
+        Expr::object([("book-id", Expr::null())]).unwrap_err(),
+        invalid("book-id")
+    );
+    assert_eq!(
+        Expr::object([("not", Expr::null())]).unwrap_err(),
+        invalid("not")
+    );
+}
+
+#[test]
+fn build_query_with_non_finite_numbers() {
+    assert_eq!(
+        Expr::try_from(1.5).unwrap().to_string(),
+        "1.5",
+        "Finite numbers are rendered as literals"
+    );
+    for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        assert!(matches!(
+            Expr::try_from(value),
+            Err(EventQlError::NonFiniteNumber { .. })
+        ));
+    }
+}
+
+#[test]
+fn check_reports_position_of_error() {
+    let cases = [
+        (
+            "SELECT * FROM events",
+            (1, 1),
+            "a query has to start with FROM",
+        ),
+        (
+            "\n  PROJECT INTO e",
+            (2, 3),
+            "a query has to start with FROM",
+        ),
+        (
+            "FROM e IN events\nWHERE (e.data.value > 1\nPROJECT INTO e",
+            (2, 7),
+            "missing ')' for this bracket",
+        ),
+        (
+            "FROM e IN events PROJECT INTO { id: e.id ]",
+            (1, 42),
+            "expected '}' but found ']'",
+        ),
+        (
+            "FROM e IN events PROJECT INTO e)",
+            (1, 32),
+            "unexpected ')'",
+        ),
+        (
+            "FROM e IN events WHERE e.type == \"x PROJECT INTO e",
+            (1, 34),
+            "unterminated string",
+        ),
+        (
+            "FROM e IN events\nWHERE e.data.text == \"a\\\nb)\" AND (\nPROJECT INTO e",
+            (3, 9),
+            "missing ')' for this bracket",
+        ),
+    ];
+    for (query, (line, column), message) in cases {
+        let error = eventql::check(query).expect_err(query);
+        assert_eq!(
+            (error.line, error.column),
+            (line, column),
+            "{query:?}: {error}"
+        );
+        assert_eq!(error.message, message, "{query:?}");
+    }
+}
+
+#[test]
+fn check_rejects_deeply_nested_queries() {
+    let nested_queries = [
+        format!("FROM e IN events PROJECT INTO {}1", "(".repeat(100_000)),
+        format!("FROM e IN events PROJECT INTO {}1", "[".repeat(100_000)),
+        format!("FROM e IN events PROJECT INTO {}1", "{a: ".repeat(100_000)),
+        format!(
+            "FROM e IN events PROJECT INTO {}1",
+            "COUNT(".repeat(100_000)
+        ),
+        format!("FROM e IN events PROJECT INTO e{}", "[e".repeat(100_000)),
+        format!("{}events PROJECT INTO e", "FROM e IN (".repeat(100_000)),
+    ];
+    for query in nested_queries {
+        let error = eventql::check(&query).expect_err("Expected a syntax error");
+        assert_eq!(
+            error.message, "the query is nested more than 64 levels deep",
+            "Unexpected error: {error}"
+        );
+    }
+
+    let query = format!(
+        "FROM e IN events PROJECT INTO {}1{}",
+        "(".repeat(64),
+        ")".repeat(64)
+    );
+    assert!(eventql::check(&query).is_ok());
+    let query = format!(
+        "FROM e IN events WHERE {}TRUE PROJECT INTO {}1",
+        "NOT ".repeat(100_000),
+        "-".repeat(100_000)
+    );
+    assert!(eventql::check(&query).is_ok());
+}
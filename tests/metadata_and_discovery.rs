@@ -1,9 +1,15 @@
 mod utils;
-use eventsourcingdb::{Event, container::Container};
-use futures::StreamExt;
+use eventsourcingdb::{
+    Event,
+    client::Client,
+    container::Container,
+    error::{ClientError, EventError},
+    event::{EventStreamExt, SignatureVerification, UnsignedEventPolicy},
+};
+use futures::{StreamExt, TryStreamExt, stream};
 use serde_json::json;
 use tokio_test::assert_err;
-use utils::create_test_container;
+use utils::{create_test_container, create_test_eventcandidate};
 
 #[tokio::test]
 async fn register_event_schema() {
@@ -65,6 +71,49 @@ async fn list_all_subjects() {
 
 //TODO!: add list scoped subjects test after writing to db
 
+#[tokio::test]
+async fn build_subject_tree() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let _ = client
+        .write_events(
+            vec![
+                create_test_eventcandidate("/books/42", json!({"value": 1})),
+                create_test_eventcandidate("/books/42/loans", json!({"value": 1})),
+                create_test_eventcandidate("/books/43/loans", json!({"value": 1})),
+                create_test_eventcandidate("/users/jane", json!({"value": 1})),
+            ],
+            vec![],
+        )
+        .await
+        .expect("Failed to write events");
+
+    let tree = client
+        .subject_tree(Some("/books"))
+        .await
+        .expect("Failed to build subject tree");
+    let books: Vec<_> = tree
+        .descendants(Some(1))
+        .into_iter()
+        .map(|node| node.subject().to_string())
+        .collect();
+    assert_eq!(books, ["/books/42", "/books/43"]);
+    assert_eq!(tree.root().descendant_count(), 4);
+    let loans: Vec<_> = tree
+        .matching("/books/*/loans")
+        .expect("Invalid pattern")
+        .into_iter()
+        .map(|node| node.subject().to_string())
+        .collect();
+    assert_eq!(loans, ["/books/42/loans", "/books/43/loans"]);
+
+    let result = client.subject_tree(Some("/books/")).await;
+    assert!(
+        matches!(result, Err(ClientError::InvalidSubject(_))),
+        "Expected an invalid subject, but got: {result:?}"
+    );
+}
+
 #[tokio::test]
 async fn list_all_event_types() {
     let container = create_test_container().await;
@@ -242,4 +291,227 @@ async fn verify_event_signature_with_broken_signature() {
     );
 }
 
+#[tokio::test]
+async fn verify_signatures_of_event_stream() {
+    let container = Container::builder()
+        .with_image_tag("preview")
+        .with_signing_key()
+        .start()
+        .await
+        .expect("Failed to start test container");
+    let verifying_key = container.get_verifying_key().unwrap();
+    let client = container.get_client().await.unwrap();
+    let event_candidate = utils::create_test_eventcandidate("/test", json!({"value": 1}));
+    let written = client
+        .write_events(vec![event_candidate], vec![])
+        .await
+        .expect("Unable to write event");
+    let event_string = serde_json::to_string(&written[0]).unwrap();
+    let broken_event_string = event_string.replace(written[0].signature().unwrap(), "BROKEN");
+    let broken_event: Event = serde_json::from_str(&broken_event_string).unwrap();
+
+    let events: Vec<_> = stream::iter([Ok(written[0].clone()), Ok(broken_event)])
+        .verify_signatures(verifying_key)
+        .collect()
+        .await;
+    assert!(matches!(&events[0], Ok(event) if event == &written[0]));
+    assert!(
+        matches!(
+            &events[1],
+            Err(ClientError::EventError(EventError::MalformedSignature))
+        ),
+        "Signature verification should have failed, but got: {:?}",
+        events[1]
+    );
+}
+
+#[tokio::test]
+async fn verify_signatures_of_all_events_read_by_client() {
+    let container = Container::builder()
+        .with_image_tag("preview")
+        .with_signing_key()
+        .start()
+        .await
+        .expect("Failed to start test container");
+    let client = Client::builder()
+        .base_url(container.get_base_url().await.unwrap())
+        .api_token(container.get_api_token())
+        .signature_verification(
+            SignatureVerification::builder()
+                .verifying_key(*container.get_verifying_key().unwrap())
+                .build(),
+        )
+        .build();
+    let event_candidate = utils::create_test_eventcandidate("/test", json!({"value": 1}));
+    let written = client
+        .write_events(vec![event_candidate], vec![])
+        .await
+        .expect("Unable to write event");
+
+    let events: Vec<_> = client
+        .read_events("/test", None)
+        .await
+        .expect("Failed to read events")
+        .try_collect()
+        .await
+        .expect("Signature verification failed");
+    assert_eq!(events, written);
+}
+
+#[tokio::test]
+async fn verify_signatures_of_unsigned_events() {
+    let container = create_test_container().await;
+    let event_candidate = utils::create_test_eventcandidate("/test", json!({"value": 1}));
+    let _ = container
+        .get_client()
+        .await
+        .unwrap()
+        .write_events(vec![event_candidate], vec![])
+        .await
+        .expect("Unable to write event");
+
+    let base_url = container.get_base_url().await.unwrap();
+    let client_with_policy = |unsigned_events| {
+        Client::builder()
+            .base_url(base_url.clone())
+            .api_token(container.get_api_token())
+            .signature_verification(
+                SignatureVerification::builder()
+                    .verifying_key(Default::default())
+                    .unsigned_events(unsigned_events)
+                    .build(),
+            )
+            .build()
+    };
+
+    let events: Vec<_> = client_with_policy(UnsignedEventPolicy::Reject)
+        .read_events("/test", None)
+        .await
+        .expect("Failed to read events")
+        .collect()
+        .await;
+    assert!(
+        matches!(
+            events.as_slice(),
+            [Err(ClientError::EventError(EventError::MissingSignature))]
+        ),
+        "Unsigned events should have been rejected, but got: {events:?}"
+    );
+
+    let events: Vec<_> = client_with_policy(UnsignedEventPolicy::Skip)
+        .read_events("/test", None)
+        .await
+        .expect("Failed to read events")
+        .collect()
+        .await;
+    assert!(
+        events.is_empty(),
+        "Unsigned events should have been skipped"
+    );
+
+    let events: Vec<_> = client_with_policy(UnsignedEventPolicy::PassThrough)
+        .read_events("/test", None)
+        .await
+        .expect("Failed to read events")
+        .try_collect()
+        .await
+        .expect("Unsigned events should have been passed through");
+    assert_eq!(events.len(), 1);
+}
+
 // TODO!: add list event types test after writing to db
+
+#[cfg(feature = "schemars")]
+#[tokio::test]
+async fn register_event_schema_for_type() {
+    use eventsourcingdb::client::event_schema_for;
+
+    #[derive(schemars::JsonSchema)]
+    #[allow(dead_code)]
+    struct BookAcquired {
+        title: String,
+        author: String,
+    }
+
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let test_event_type = "io.eventsourcingdb.test";
+    client
+        .register_event_schema_for::<BookAcquired>(test_event_type)
+        .await
+        .expect("Failed to register event schema");
+    let event_type = client
+        .read_event_type(test_event_type)
+        .await
+        .expect("Failed to read event type");
+    assert_eq!(
+        event_type.schema,
+        Some(event_schema_for::<BookAcquired>()),
+        "Expected the generated schema to be registered"
+    );
+}
+
+#[cfg(feature = "schemars")]
+#[tokio::test]
+async fn compare_event_schema_for_type() {
+    use eventsourcingdb::client::EventSchemaComparison;
+
+    #[derive(schemars::JsonSchema)]
+    #[allow(dead_code)]
+    struct BookAcquired {
+        title: String,
+    }
+
+    #[derive(schemars::JsonSchema)]
+    #[allow(dead_code)]
+    struct BookAcquiredWithAuthor {
+        title: String,
+        author: String,
+    }
+
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let test_event_type = "io.eventsourcingdb.test";
+
+    let comparison = client
+        .compare_event_schema_for::<BookAcquired>(test_event_type)
+        .await
+        .expect("Failed to compare event schema");
+    assert!(
+        matches!(comparison, EventSchemaComparison::NotRegistered { .. }),
+        "Expected no schema to be registered, but got: {comparison:?}"
+    );
+    let event_types: Vec<_> = client
+        .list_event_types()
+        .await
+        .expect("Failed to list event types")
+        .collect()
+        .await;
+    assert!(
+        event_types.is_empty(),
+        "Expected the dry run not to register anything, but got: {event_types:?}"
+    );
+
+    client
+        .register_event_schema_for::<BookAcquired>(test_event_type)
+        .await
+        .expect("Failed to register event schema");
+
+    let comparison = client
+        .compare_event_schema_for::<BookAcquired>(test_event_type)
+        .await
+        .expect("Failed to compare event schema");
+    assert!(
+        comparison.is_unchanged(),
+        "Expected the schema to be unchanged, but got: {comparison:?}"
+    );
+
+    let comparison = client
+        .compare_event_schema_for::<BookAcquiredWithAuthor>(test_event_type)
+        .await
+        .expect("Failed to compare event schema");
+    assert!(
+        matches!(comparison, EventSchemaComparison::Changed { .. }),
+        "Expected the schema to be changed, but got: {comparison:?}"
+    );
+}
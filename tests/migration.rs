@@ -0,0 +1,215 @@
+mod utils;
+
+use eventsourcingdb::{
+    event::Event,
+    migration::{MigrationOptions, migrate, migrate_with},
+    request_options::ReadEventsOptions,
+};
+use futures::TryStreamExt;
+use serde_json::json;
+use utils::{create_test_container, create_test_eventcandidate};
+
+#[tokio::test]
+async fn migrate_subject_tree() {
+    let source_container = create_test_container().await;
+    let source = source_container.get_client().await.unwrap();
+    let target_container = create_test_container().await;
+    let target = target_container.get_client().await.unwrap();
+    let _ = source
+        .write_events(
+            vec![
+                create_test_eventcandidate("/books/1", json!({"value": 1})),
+                create_test_eventcandidate("/authors/1", json!({"value": 2})),
+                create_test_eventcandidate("/books/2", json!({"value": 3})),
+                create_test_eventcandidate("/books/1", json!({"value": 4})),
+            ],
+            vec![],
+        )
+        .await
+        .expect("Unable to write events");
+    let options = MigrationOptions { recursive: true };
+
+    let report = migrate(&source, &target, &["/books"], options.clone())
+        .await
+        .expect("Failed to migrate events");
+    assert_eq!(report.migrated_subjects, vec!["/books/1", "/books/2"]);
+    assert!(report.skipped_subjects.is_empty());
+    assert_eq!(report.event_count, 3);
+
+    let events: Vec<Event> = target
+        .read_events("/books/1", None)
+        .await
+        .expect("Failed to read events")
+        .try_collect()
+        .await
+        .expect("Failed to read events");
+    let data: Vec<_> = events.iter().map(Event::data).cloned().collect();
+    assert_eq!(data, vec![json!({"value": 1}), json!({"value": 4})]);
+
+    let report = migrate(&source, &target, &["/books"], options)
+        .await
+        .expect("Failed to migrate events");
+    assert!(report.migrated_subjects.is_empty());
+    assert_eq!(report.skipped_subjects, vec!["/books/1", "/books/2"]);
+    assert_eq!(report.event_count, 0);
+}
+
+#[tokio::test]
+async fn migrate_resumes_partially_migrated_subjects() {
+    let source_container = create_test_container().await;
+    let source = source_container.get_client().await.unwrap();
+    let target_container = create_test_container().await;
+    let target = target_container.get_client().await.unwrap();
+    let _ = source
+        .write_events(
+            vec![
+                create_test_eventcandidate("/books/1", json!({"value": 1})),
+                create_test_eventcandidate("/books/2", json!({"value": 2})),
+                create_test_eventcandidate("/books/1", json!({"value": 3})),
+            ],
+            vec![],
+        )
+        .await
+        .expect("Unable to write events");
+    // A previous run that failed after writing the first event.
+    let _ = target
+        .write_events(
+            vec![create_test_eventcandidate("/books/1", json!({"value": 1}))],
+            vec![],
+        )
+        .await
+        .expect("Unable to write events");
+
+    let report = migrate(
+        &source,
+        &target,
+        &["/books"],
+        MigrationOptions { recursive: true },
+    )
+    .await
+    .expect("Failed to migrate events");
+    assert_eq!(report.migrated_subjects, vec!["/books/1", "/books/2"]);
+    assert!(report.skipped_subjects.is_empty());
+    assert_eq!(report.event_count, 2);
+
+    let events: Vec<Event> = target
+        .read_events("/books/1", None)
+        .await
+        .expect("Failed to read events")
+        .try_collect()
+        .await
+        .expect("Failed to read events");
+    let data: Vec<_> = events.iter().map(Event::data).cloned().collect();
+    assert_eq!(data, vec![json!({"value": 1}), json!({"value": 3})]);
+}
+
+#[tokio::test]
+async fn migrate_keeps_order_across_subjects() {
+    let source_container = create_test_container().await;
+    let source = source_container.get_client().await.unwrap();
+    let target_container = create_test_container().await;
+    let target = target_container.get_client().await.unwrap();
+    let _ = source
+        .write_events(
+            vec![
+                create_test_eventcandidate("/books/2", json!({"value": 1})),
+                create_test_eventcandidate("/authors/1", json!({"value": 2})),
+                create_test_eventcandidate("/books/1", json!({"value": 3})),
+                create_test_eventcandidate("/authors/1", json!({"value": 4})),
+                create_test_eventcandidate("/books/2", json!({"value": 5})),
+            ],
+            vec![],
+        )
+        .await
+        .expect("Unable to write events");
+    let options = MigrationOptions { recursive: true };
+
+    let report = migrate(
+        &source,
+        &target,
+        &["/books", "/authors", "/books/2"],
+        options,
+    )
+    .await
+    .expect("Failed to migrate events");
+    assert_eq!(
+        report.migrated_subjects,
+        vec!["/authors/1", "/books/1", "/books/2"]
+    );
+    assert_eq!(report.event_count, 5);
+
+    let events: Vec<Event> = target
+        .read_events(
+            "/",
+            Some(ReadEventsOptions {
+                recursive: true,
+                ..Default::default()
+            }),
+        )
+        .await
+        .expect("Failed to read events")
+        .try_collect()
+        .await
+        .expect("Failed to read events");
+    let migrated: Vec<_> = events
+        .iter()
+        .map(|event| (event.subject(), event.data()["value"].as_u64()))
+        .collect();
+    assert_eq!(
+        migrated,
+        vec![
+            ("/books/2", Some(1)),
+            ("/authors/1", Some(2)),
+            ("/books/1", Some(3)),
+            ("/authors/1", Some(4)),
+            ("/books/2", Some(5)),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn migrate_with_transformation() {
+    let source_container = create_test_container().await;
+    let source = source_container.get_client().await.unwrap();
+    let target_container = create_test_container().await;
+    let target = target_container.get_client().await.unwrap();
+    let _ = source
+        .write_events(
+            vec![
+                create_test_eventcandidate("/books/1", json!({"value": 1, "secret": "a"})),
+                create_test_eventcandidate("/books/1", json!({"value": 2, "secret": "b"})),
+            ],
+            vec![],
+        )
+        .await
+        .expect("Unable to write events");
+
+    let report = migrate_with(
+        &source,
+        &target,
+        &["/books/1"],
+        MigrationOptions::default(),
+        |mut event| {
+            if event.data["value"] == 2 {
+                return None;
+            }
+            event.ty = "io.eventsourcingdb.migrated".to_string();
+            let _ = event.data.as_object_mut().unwrap().remove("secret");
+            Some(event)
+        },
+    )
+    .await
+    .expect("Failed to migrate events");
+    assert_eq!(report.event_count, 1);
+
+    let events: Vec<Event> = target
+        .read_events("/books/1", None)
+        .await
+        .expect("Failed to read events")
+        .try_collect()
+        .await
+        .expect("Failed to read events");
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].ty(), "io.eventsourcingdb.migrated");
+    assert_eq!(events[0].data(), &json!({"value": 1}));
+}
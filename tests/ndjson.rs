@@ -0,0 +1,89 @@
+mod utils;
+
+use eventsourcingdb::{
+    event::{Event, EventCandidate, TraceInfo},
+    ndjson::{ImportOptions, export_events, import_events},
+    request_options::ReadEventsOptions,
+};
+use futures::TryStreamExt;
+use serde_json::json;
+use utils::{create_test_container, create_test_eventcandidate};
+
+#[tokio::test]
+async fn export_and_import_events() {
+    let source_container = create_test_container().await;
+    let source = source_container.get_client().await.unwrap();
+    let target_container = create_test_container().await;
+    let target = target_container.get_client().await.unwrap();
+    let written = source
+        .write_events(
+            vec![
+                create_test_eventcandidate("/books/1", json!({"value": 1})),
+                create_test_eventcandidate("/authors/1", json!({"value": 2})),
+                EventCandidate::builder()
+                    .source("https://www.eventsourcingdb.io".to_string())
+                    .data(json!({"value": 3}))
+                    .subject("/books/2".to_string())
+                    .ty("io.eventsourcingdb.test".to_string())
+                    .traceinfo(TraceInfo::Traceparent {
+                        traceparent: "00-01234567012345670123456701234567-0123456701234567-00"
+                            .to_string(),
+                    })
+                    .build(),
+            ],
+            vec![],
+        )
+        .await
+        .expect("Unable to write events");
+
+    let mut backup = Vec::new();
+    let options = ReadEventsOptions {
+        recursive: true,
+        ..Default::default()
+    };
+    let exported = export_events(&source, "/books", Some(options.clone()), &mut backup)
+        .await
+        .expect("Failed to export events");
+    assert_eq!(exported, 2);
+    let lines: Vec<Event> = String::from_utf8(backup.clone())
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("Failed to parse line"))
+        .collect();
+    assert_eq!(lines, vec![written[0].clone(), written[2].clone()]);
+
+    let imported = import_events(&target, backup.as_slice(), ImportOptions { batch_size: 1 })
+        .await
+        .expect("Failed to import events");
+    assert_eq!(imported, 2);
+
+    let events: Vec<Event> = target
+        .read_events("/books", Some(options))
+        .await
+        .expect("Failed to read events")
+        .try_collect()
+        .await
+        .expect("Failed to read events");
+    let candidates: Vec<EventCandidate> = events.into_iter().map(Into::into).collect();
+    let expected: Vec<EventCandidate> = vec![written[0].clone().into(), written[2].clone().into()];
+    assert_eq!(candidates, expected);
+}
+
+#[tokio::test]
+async fn import_events_skips_empty_lines() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let written = client
+        .write_events(
+            vec![create_test_eventcandidate("/books/1", json!({"value": 1}))],
+            vec![],
+        )
+        .await
+        .expect("Unable to write events");
+    let backup = format!("\n{}\n\n", serde_json::to_string(&written[0]).unwrap());
+
+    let imported = import_events(&client, backup.as_bytes(), ImportOptions::default())
+        .await
+        .expect("Failed to import events");
+    assert_eq!(imported, 1);
+}
@@ -1,5 +1,14 @@
 mod utils;
+use std::time::Duration;
+
+use eventsourcingdb::{
+    client::{Client, RetryPolicy, SubscriptionItem},
+    error::{ClientError, EventError},
+    event::SignatureVerification,
+    request_options::SubscribeOptions,
+};
 use futures::stream::StreamExt;
+use serde::Deserialize;
 use serde_json::json;
 use utils::create_test_container;
 use utils::create_test_eventcandidate;
@@ -50,3 +59,345 @@ async fn keep_observing_events() {
 
     assert_eq!(vec![event], written);
 }
+
+#[tokio::test]
+async fn observe_events_resilient() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let first_candidate = create_test_eventcandidate("/test", json!({"value": 1}));
+    let mut written = client
+        .write_events(vec![first_candidate], vec![])
+        .await
+        .expect("Unable to write event");
+
+    let mut events_stream = client
+        .observe_events_resilient("/test", None, RetryPolicy::default())
+        .await
+        .expect("Failed to observe events");
+    let second_candidate = create_test_eventcandidate("/test", json!({"value": 2}));
+    written.extend(
+        client
+            .write_events(vec![second_candidate], vec![])
+            .await
+            .expect("Unable to write event"),
+    );
+
+    let events = events_stream
+        .by_ref()
+        .take(2)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Expected events, but got an error");
+
+    assert_eq!(events, written);
+}
+
+#[tokio::test]
+async fn observe_events_resilient_survives_repeatedly_lost_connections() {
+    let container = create_test_container().await;
+    // Every connection is dropped right away because no heartbeat arrives within a millisecond.
+    let client = Client::builder()
+        .base_url(container.get_base_url().await.unwrap())
+        .api_token(container.get_api_token())
+        .heartbeat_timeout(Duration::from_millis(1))
+        .build();
+    let retry_policy = RetryPolicy::builder()
+        .max_attempts(1)
+        .initial_backoff(Duration::from_millis(50))
+        .jitter(false)
+        .build();
+
+    let mut events_stream = client
+        .observe_events_resilient("/test", None, retry_policy)
+        .await
+        .expect("Failed to observe events");
+    let result = tokio::time::timeout(Duration::from_millis(500), events_stream.next()).await;
+
+    assert!(
+        result.is_err(),
+        "Expected the stream to keep reconnecting, but got: {result:?}"
+    );
+}
+
+#[tokio::test]
+async fn observe_events_resilient_ends_with_error_if_reconnecting_fails() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let retry_policy = RetryPolicy::builder()
+        .max_attempts(2)
+        .initial_backoff(Duration::from_millis(10))
+        .build();
+
+    let mut events_stream = client
+        .observe_events_resilient("/test", None, retry_policy)
+        .await
+        .expect("Failed to observe events");
+    container.stop().await.expect("Failed to stop container");
+    let result = events_stream.next().await;
+
+    assert!(
+        matches!(result, Some(Err(_))),
+        "Expected an error after reconnecting failed, but got: {result:?}"
+    );
+    assert!(events_stream.next().await.is_none());
+}
+
+#[tokio::test]
+async fn observe_events_resilient_continues_after_rejected_events() {
+    let container = create_test_container().await;
+    let client = Client::builder()
+        .base_url(container.get_base_url().await.unwrap())
+        .api_token(container.get_api_token())
+        .signature_verification(
+            SignatureVerification::builder()
+                .verifying_key(Default::default())
+                .build(),
+        )
+        .build();
+    let _ = client
+        .write_events(
+            vec![create_test_eventcandidate("/test", json!({"value": 1}))],
+            vec![],
+        )
+        .await
+        .expect("Unable to write event");
+
+    let mut events_stream = client
+        .observe_events_resilient("/test", None, RetryPolicy::default())
+        .await
+        .expect("Failed to observe events");
+    let _ = client
+        .write_events(
+            vec![create_test_eventcandidate("/test", json!({"value": 2}))],
+            vec![],
+        )
+        .await
+        .expect("Unable to write event");
+
+    for _ in 0..2 {
+        let result = events_stream
+            .next()
+            .await
+            .expect("Expected the stream to continue after a rejected event");
+        assert!(
+            matches!(
+                result,
+                Err(ClientError::EventError(EventError::MissingSignature))
+            ),
+            "Expected the unsigned event to be rejected, but got: {result:?}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn observe_events_without_heartbeat_times_out() {
+    let container = create_test_container().await;
+    let client = Client::builder()
+        .base_url(container.get_base_url().await.unwrap())
+        .api_token(container.get_api_token())
+        .heartbeat_timeout(Duration::from_millis(1))
+        .build();
+
+    let mut events_stream = client
+        .observe_events("/test", None)
+        .await
+        .expect("Failed to observe events");
+    let result = events_stream.next().await;
+
+    assert!(
+        matches!(result, Some(Err(ClientError::HeartbeatTimeout(_)))),
+        "Expected a heartbeat timeout, but got: {result:?}"
+    );
+    assert!(
+        events_stream.next().await.is_none(),
+        "Expected the stream to end after the heartbeat timeout"
+    );
+}
+
+#[tokio::test]
+async fn observe_events_tracks_heartbeats_per_stream() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+
+    let mut watched_stream = client
+        .observe_events("/test", None)
+        .await
+        .expect("Failed to observe events");
+    let other_stream = client
+        .observe_events("/test", None)
+        .await
+        .expect("Failed to observe events");
+    assert_eq!(watched_stream.last_heartbeat(), None);
+
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(30);
+    while watched_stream.last_heartbeat().is_none() && tokio::time::Instant::now() < deadline {
+        let _ = tokio::time::timeout(Duration::from_millis(100), watched_stream.next()).await;
+    }
+
+    assert!(
+        watched_stream.last_heartbeat().is_some(),
+        "Expected a heartbeat on the polled stream"
+    );
+    assert_eq!(other_stream.last_heartbeat(), None);
+}
+
+#[tokio::test]
+async fn subscribe_to_past_and_new_events() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let past = client
+        .write_events(
+            vec![
+                create_test_eventcandidate("/test/1", json!({"value": 1})),
+                create_test_eventcandidate("/test/2", json!({"value": 2})),
+            ],
+            vec![],
+        )
+        .await
+        .expect("Unable to write events");
+
+    let options = SubscribeOptions {
+        recursive: true,
+        ..Default::default()
+    };
+    let mut item_stream = client
+        .subscribe("/test", Some(options))
+        .await
+        .expect("Failed to subscribe");
+    let mut items = Vec::new();
+    for _ in 0..3 {
+        items.push(
+            item_stream
+                .next()
+                .await
+                .expect("Failed to read items")
+                .expect("Expected an item, but got an error"),
+        );
+    }
+    assert_eq!(
+        items,
+        vec![
+            SubscriptionItem::Event(Box::new(past[0].clone())),
+            SubscriptionItem::Event(Box::new(past[1].clone())),
+            SubscriptionItem::CaughtUp,
+        ]
+    );
+
+    let new = client
+        .write_events(
+            vec![create_test_eventcandidate("/test/3", json!({"value": 3}))],
+            vec![],
+        )
+        .await
+        .expect("Unable to write event");
+    let item = item_stream
+        .next()
+        .await
+        .expect("Failed to read items")
+        .expect("Expected an item, but got an error");
+    assert_eq!(item, SubscriptionItem::Event(Box::new(new[0].clone())));
+}
+
+#[tokio::test]
+async fn subscribe_without_past_events_is_caught_up_immediately() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+
+    let mut item_stream = client
+        .subscribe("/test", None)
+        .await
+        .expect("Failed to subscribe");
+    let item = item_stream
+        .next()
+        .await
+        .expect("Failed to read items")
+        .expect("Expected an item, but got an error");
+    assert_eq!(item, SubscriptionItem::CaughtUp);
+}
+
+#[tokio::test]
+async fn subscribe_continues_after_rejected_events() {
+    let container = create_test_container().await;
+    let client = Client::builder()
+        .base_url(container.get_base_url().await.unwrap())
+        .api_token(container.get_api_token())
+        .signature_verification(
+            SignatureVerification::builder()
+                .verifying_key(Default::default())
+                .build(),
+        )
+        .build();
+    let _ = client
+        .write_events(
+            vec![create_test_eventcandidate("/test", json!({"value": 1}))],
+            vec![],
+        )
+        .await
+        .expect("Unable to write event");
+
+    let items: Vec<_> = client
+        .subscribe("/test", None)
+        .await
+        .expect("Failed to subscribe")
+        .take(2)
+        .collect()
+        .await;
+    assert!(
+        matches!(
+            items.as_slice(),
+            [
+                Err(ClientError::EventError(EventError::MissingSignature)),
+                Ok(SubscriptionItem::CaughtUp)
+            ]
+        ),
+        "Expected the unsigned event to be rejected before catching up, but got: {items:?}"
+    );
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Counter {
+    value: u32,
+}
+
+#[tokio::test]
+async fn observe_events_typed() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let written = client
+        .write_events(
+            vec![
+                create_test_eventcandidate("/test", json!({"value": 1})),
+                create_test_eventcandidate("/test", json!({"value": "not a number"})),
+            ],
+            vec![],
+        )
+        .await
+        .expect("Unable to write events");
+
+    let mut events_stream = client
+        .observe_events_typed::<Counter>("/test", None)
+        .await
+        .expect("Failed to request events");
+
+    let event = events_stream
+        .next()
+        .await
+        .expect("Failed to observe events")
+        .expect("Expected a typed event, but got an error");
+    assert_eq!(event.data(), &Counter { value: 1 });
+    assert_eq!(event.event(), &written[0]);
+
+    let mismatch = events_stream
+        .next()
+        .await
+        .expect("Failed to observe events");
+    assert!(
+        matches!(
+            mismatch,
+            Err(ClientError::EventError(EventError::SerdeError(_)))
+        ),
+        "Expected a deserialization error, but got: {mismatch:?}"
+    );
+}
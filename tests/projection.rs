@@ -0,0 +1,146 @@
+mod utils;
+
+use std::{convert::Infallible, time::Duration};
+
+use eventsourcingdb::{
+    event::Event,
+    projection::{
+        CheckpointStore, FileCheckpointStore, InMemoryCheckpointStore, Projection, ProjectionRunner,
+    },
+};
+use serde_json::json;
+use utils::{create_test_container, create_test_eventcandidate};
+
+#[derive(Debug, Default)]
+struct Subjects {
+    processed: Vec<String>,
+}
+
+impl Projection for Subjects {
+    type Error = Infallible;
+
+    async fn apply(&mut self, event: &Event) -> Result<(), Infallible> {
+        self.processed.push(event.subject().to_string());
+        Ok(())
+    }
+}
+
+/// Runs the projection until it has caught up with the events written so far.
+async fn run_briefly<P: Projection, S: CheckpointStore>(runner: &mut ProjectionRunner<P, S>) {
+    let _ = tokio::time::timeout(Duration::from_secs(1), runner.run()).await;
+}
+
+#[tokio::test]
+async fn run_projection_over_subject_tree() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let written = client
+        .write_events(
+            vec![
+                create_test_eventcandidate("/books/1", json!({"value": 1})),
+                create_test_eventcandidate("/authors/1", json!({"value": 2})),
+                create_test_eventcandidate("/books/2", json!({"value": 3})),
+            ],
+            vec![],
+        )
+        .await
+        .expect("Failed to write events");
+
+    let mut runner = ProjectionRunner::builder()
+        .client(client)
+        .name("books")
+        .subject("/books")
+        .projection(Subjects::default())
+        .checkpoint_store(InMemoryCheckpointStore::new())
+        .build();
+    run_briefly(&mut runner).await;
+
+    assert_eq!(runner.projection().processed, vec!["/books/1", "/books/2"]);
+    let checkpoint = runner
+        .checkpoint_store()
+        .load("books")
+        .await
+        .expect("Failed to load checkpoint");
+    assert_eq!(checkpoint.as_deref(), Some(written[2].id()));
+}
+
+#[tokio::test]
+async fn run_projection_resumes_from_checkpoint() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let directory = std::env::temp_dir().join(format!("esdb-checkpoints-{}", std::process::id()));
+    let store = FileCheckpointStore::new(&directory);
+
+    let _ = client
+        .write_events(
+            vec![create_test_eventcandidate("/books/1", json!({"value": 1}))],
+            vec![],
+        )
+        .await
+        .expect("Failed to write events");
+    let mut runner = ProjectionRunner::builder()
+        .client(client.clone())
+        .name("books")
+        .subject("/books")
+        .projection(Subjects::default())
+        .checkpoint_store(store.clone())
+        .build();
+    run_briefly(&mut runner).await;
+    assert_eq!(runner.projection().processed, vec!["/books/1"]);
+
+    let _ = client
+        .write_events(
+            vec![create_test_eventcandidate("/books/2", json!({"value": 2}))],
+            vec![],
+        )
+        .await
+        .expect("Failed to write events");
+    let mut runner = ProjectionRunner::builder()
+        .client(client)
+        .name("books")
+        .subject("/books")
+        .projection(Subjects::default())
+        .checkpoint_store(store)
+        .build();
+    run_briefly(&mut runner).await;
+    assert_eq!(runner.projection().processed, vec!["/books/2"]);
+
+    std::fs::remove_dir_all(directory).expect("Failed to remove checkpoint directory");
+}
+
+#[tokio::test]
+async fn file_checkpoint_store_round_trip() {
+    let directory =
+        std::env::temp_dir().join(format!("esdb-checkpoints-long-{}", std::process::id()));
+    let store = FileCheckpointStore::new(&directory);
+    let name = format!("books-{}", "a".repeat(300));
+    assert_eq!(
+        store.load(&name).await.expect("Failed to load checkpoint"),
+        None
+    );
+
+    store
+        .save(&name, "1")
+        .await
+        .expect("Failed to save checkpoint");
+    store
+        .save(&name, "2")
+        .await
+        .expect("Failed to save checkpoint");
+    assert_eq!(
+        store
+            .load(&name)
+            .await
+            .expect("Failed to load checkpoint")
+            .as_deref(),
+        Some("2")
+    );
+    assert_eq!(
+        store
+            .load("books")
+            .await
+            .expect("Failed to load checkpoint"),
+        None
+    );
+    std::fs::remove_dir_all(directory).expect("Failed to remove checkpoint directory");
+}
@@ -1,9 +1,14 @@
 mod utils;
 
-use eventsourcingdb::request_options::{
-    Ordering, ReadEventMissingStrategy, ReadEventsOptions, ReadFromLatestEventOptions,
+use eventsourcingdb::{
+    error::{ClientError, EventError, IntegrityError},
+    event::EventStreamExt,
+    request_options::{
+        Ordering, ReadEventMissingStrategy, ReadEventsOptions, ReadFromLatestEventOptions,
+    },
 };
-use futures::TryStreamExt;
+use futures::{StreamExt, TryStreamExt, stream};
+use serde::Deserialize;
 use serde_json::json;
 use utils::create_test_container;
 use utils::{
@@ -332,3 +337,139 @@ async fn read_from_latest_event() {
 
     assert_eq!(events, written);
 }
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Counter {
+    value: u32,
+}
+
+#[tokio::test]
+async fn read_events_typed() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let event_candidate = create_test_eventcandidate("/test", json!({"value": 1}));
+    let written = client
+        .write_events(vec![event_candidate], vec![])
+        .await
+        .expect("Unable to write event");
+
+    let events_stream = client
+        .read_events_typed::<Counter>("/test", None)
+        .await
+        .expect("Failed to request events");
+    let events: Vec<_> = events_stream
+        .try_collect()
+        .await
+        .expect("Failed to read events");
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].data(), &Counter { value: 1 });
+    assert_eq!(events[0].event(), &written[0]);
+    events[0].verify_hash().expect("Hash verification failed");
+}
+
+#[tokio::test]
+async fn read_events_typed_with_mismatching_data() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let event_candidate = create_test_eventcandidate("/test", json!({"value": "not a number"}));
+    client
+        .write_events(vec![event_candidate], vec![])
+        .await
+        .expect("Unable to write event");
+
+    let events_stream = client
+        .read_events_typed::<Counter>("/test", None)
+        .await
+        .expect("Failed to request events");
+    let events: Result<Vec<_>, _> = events_stream.try_collect().await;
+
+    assert!(
+        matches!(
+            events,
+            Err(ClientError::EventError(EventError::SerdeError(_)))
+        ),
+        "Expected a deserialization error, but got: {events:?}"
+    );
+}
+
+#[tokio::test]
+async fn read_events_with_verified_chain() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let written = client
+        .write_events(create_numbered_eventcandidates(3), vec![])
+        .await
+        .expect("Unable to write events");
+
+    let options = ReadEventsOptions {
+        recursive: true,
+        ..Default::default()
+    };
+    let events: Vec<_> = client
+        .read_events("/", Some(options))
+        .await
+        .expect("Failed to request events")
+        .verify_chain()
+        .try_collect()
+        .await
+        .expect("Failed to verify events");
+
+    assert_eq!(events, written);
+}
+
+#[tokio::test]
+async fn verify_chain_with_missing_event() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let written = client
+        .write_events(create_numbered_eventcandidates(3), vec![])
+        .await
+        .expect("Unable to write events");
+
+    let events: Vec<_> = stream::iter([written[0].clone(), written[2].clone()].map(Ok))
+        .verify_chain()
+        .collect()
+        .await;
+
+    assert_eq!(events.len(), 2);
+    match &events[1] {
+        Err(ClientError::IntegrityError(IntegrityError::BrokenChain {
+            event_id,
+            predecessor_id,
+            ..
+        })) => {
+            assert_eq!(event_id, written[2].id());
+            assert_eq!(predecessor_id, written[0].id());
+        }
+        other => panic!("Expected a broken chain, but got: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn verify_chain_with_broken_hash() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let written = client
+        .write_events(create_numbered_eventcandidates(2), vec![])
+        .await
+        .expect("Unable to write events");
+    let event_string = serde_json::to_string(&written[1]).unwrap();
+    let broken_event = serde_json::from_str(&event_string.replace(written[1].hash(), "BROKEN"))
+        .expect("Failed to deserialize event");
+
+    let events: Vec<_> = stream::iter([Ok(written[0].clone()), Ok(broken_event)])
+        .verify_chain()
+        .collect()
+        .await;
+
+    assert!(
+        matches!(
+            &events[1],
+            Err(ClientError::IntegrityError(IntegrityError::InvalidHash { event_id, .. }))
+                if event_id == written[1].id()
+        ),
+        "Expected an invalid hash, but got: {:?}",
+        events[1]
+    );
+}
@@ -1,6 +1,11 @@
 mod utils;
-use futures::stream::TryStreamExt;
-use utils::create_test_container;
+use eventsourcingdb::{
+    error::ClientError,
+    eventql::{EventQl, Expr, Order},
+};
+use futures::stream::{StreamExt, TryStreamExt};
+use serde_json::json;
+use utils::{create_test_container, create_test_eventcandidate};
 
 #[tokio::test]
 async fn run_empty_query() {
@@ -15,3 +20,116 @@ async fn run_empty_query() {
     let rows = rows.expect("Failed to read rows");
     assert_eq!(rows.len(), 0);
 }
+
+#[tokio::test]
+async fn run_built_query_with_escaped_literal() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let title = "2001 - A \"Space\" Odyssey\n";
+    let _ = client
+        .write_events(
+            vec![
+                create_test_eventcandidate("/books/1", json!({"title": title})),
+                create_test_eventcandidate("/books/2", json!({"title": "Brave New World"})),
+            ],
+            vec![],
+        )
+        .await
+        .expect("Failed to write events");
+
+    let query = EventQl::from("e")
+        .unwrap()
+        .in_events()
+        .where_(Expr::field("e.data.title").unwrap().equals(title))
+        .order_by(Expr::field("e.time").unwrap(), Order::Ascending)
+        .project_into(Expr::object([("subject", Expr::field("e.subject").unwrap())]).unwrap());
+    let rows: Vec<_> = client
+        .run_eventql_query(query.as_str())
+        .await
+        .expect("Unable to run query")
+        .try_collect()
+        .await
+        .expect("Failed to read rows");
+    assert_eq!(rows, vec![json!({"subject": "/books/1"})]);
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize)]
+struct Book {
+    subject: String,
+    title: String,
+}
+
+#[tokio::test]
+async fn run_query_as_type() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let _ = client
+        .write_events(
+            vec![
+                create_test_eventcandidate("/books/1", json!({"title": "Brave New World"})),
+                create_test_eventcandidate("/books/2", json!({"title": "Dune"})),
+            ],
+            vec![],
+        )
+        .await
+        .expect("Failed to write events");
+
+    let rows: Vec<Book> = client
+        .run_eventql_query_as(
+            "FROM e IN events ORDER BY e.time ASC PROJECT INTO { subject: e.subject, title: e.data.title }",
+        )
+        .await
+        .expect("Unable to run query")
+        .try_collect()
+        .await
+        .expect("Failed to read rows");
+    assert_eq!(
+        rows,
+        vec![
+            Book {
+                subject: "/books/1".to_string(),
+                title: "Brave New World".to_string(),
+            },
+            Book {
+                subject: "/books/2".to_string(),
+                title: "Dune".to_string(),
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn run_query_as_type_reports_invalid_rows() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    let _ = client
+        .write_events(
+            vec![
+                create_test_eventcandidate("/books/1", json!({"title": "Brave New World"})),
+                create_test_eventcandidate("/books/2", json!({"title": 42})),
+                create_test_eventcandidate("/books/3", json!({"title": "Dune"})),
+            ],
+            vec![],
+        )
+        .await
+        .expect("Failed to write events");
+
+    let rows: Vec<_> = client
+        .run_eventql_query_as::<Book>(
+            "FROM e IN events ORDER BY e.time ASC PROJECT INTO { subject: e.subject, title: e.data.title }",
+        )
+        .await
+        .expect("Unable to run query")
+        .collect()
+        .await;
+    assert_eq!(rows.len(), 3);
+    assert!(rows[0].is_ok());
+    assert!(rows[2].is_ok(), "Expected later rows to be delivered");
+    match &rows[1] {
+        Err(ClientError::RowDeserializationError(error)) => {
+            assert_eq!(error.index, 1);
+            assert_eq!(error.row, json!({"subject": "/books/2", "title": 42}));
+        }
+        other => panic!("Expected a row deserialization error, but got: {other:?}"),
+    }
+}
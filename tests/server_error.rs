@@ -0,0 +1,159 @@
+use eventsourcingdb::error::{ClientError, ServerError, ServerErrorKind};
+use reqwest::StatusCode;
+use serde_json::json;
+
+#[test]
+fn parse_error_bodies() {
+    let cases = [
+        (
+            r#"{"error": "precondition failed", "code": 42}"#,
+            "precondition failed",
+            Some(json!({"error": "precondition failed", "code": 42})),
+        ),
+        (
+            r#"{"message": "event not found"}"#,
+            "event not found",
+            Some(json!({"message": "event not found"})),
+        ),
+        (
+            r#"{"detail": "invalid subject"}"#,
+            "invalid subject",
+            Some(json!({"detail": "invalid subject"})),
+        ),
+        (
+            r#"{"reason": "unknown"}"#,
+            r#"{"reason": "unknown"}"#,
+            Some(json!({"reason": "unknown"})),
+        ),
+        (r#""quoted message""#, "quoted message", None),
+        ("  plain text message\n", "plain text message", None),
+        ("", "Bad Request", None),
+        ("   ", "Bad Request", None),
+    ];
+    for (body, message, details) in cases {
+        let error = ServerError::new(Some(StatusCode::BAD_REQUEST), body);
+        assert_eq!(error.message(), message, "{body:?}");
+        assert_eq!(error.details(), details.as_ref(), "{body:?}");
+        assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST), "{body:?}");
+    }
+}
+
+#[test]
+fn parse_empty_body_of_streamed_error() {
+    let error = ServerError::new(None, "");
+    assert_eq!(error.message(), "");
+    assert_eq!(error.status(), None);
+    assert_eq!(error.kind(), ServerErrorKind::Other);
+}
+
+#[test]
+fn classify_errors() {
+    let cases = [
+        (
+            Some(StatusCode::CONFLICT),
+            "",
+            ServerErrorKind::PreconditionFailed,
+        ),
+        (
+            Some(StatusCode::PRECONDITION_FAILED),
+            "",
+            ServerErrorKind::PreconditionFailed,
+        ),
+        (
+            Some(StatusCode::UNAUTHORIZED),
+            "",
+            ServerErrorKind::Unauthorized,
+        ),
+        (Some(StatusCode::FORBIDDEN), "", ServerErrorKind::Forbidden),
+        (Some(StatusCode::NOT_FOUND), "", ServerErrorKind::NotFound),
+        (
+            Some(StatusCode::BAD_REQUEST),
+            "data does not match Schema",
+            ServerErrorKind::SchemaViolation,
+        ),
+        (
+            Some(StatusCode::UNPROCESSABLE_ENTITY),
+            "schema violated",
+            ServerErrorKind::SchemaViolation,
+        ),
+        (
+            Some(StatusCode::BAD_REQUEST),
+            "malformed request",
+            ServerErrorKind::BadRequest,
+        ),
+        (
+            Some(StatusCode::UNPROCESSABLE_ENTITY),
+            "",
+            ServerErrorKind::BadRequest,
+        ),
+        (
+            Some(StatusCode::INTERNAL_SERVER_ERROR),
+            "",
+            ServerErrorKind::Internal,
+        ),
+        (
+            Some(StatusCode::SERVICE_UNAVAILABLE),
+            "",
+            ServerErrorKind::Internal,
+        ),
+        (
+            Some(StatusCode::IM_A_TEAPOT),
+            "precondition failed",
+            ServerErrorKind::Other,
+        ),
+        (
+            None,
+            "Precondition failed",
+            ServerErrorKind::PreconditionFailed,
+        ),
+        (
+            None,
+            "event does not match schema",
+            ServerErrorKind::SchemaViolation,
+        ),
+        (None, "subject not found", ServerErrorKind::NotFound),
+        (None, "something went wrong", ServerErrorKind::Other),
+    ];
+    for (status, body, kind) in cases {
+        let error = ServerError::new(status, body);
+        assert_eq!(error.kind(), kind, "{status:?} {body:?}");
+    }
+}
+
+#[test]
+fn classify_client_errors() {
+    let cases = [
+        (StatusCode::CONFLICT, ServerErrorKind::PreconditionFailed),
+        (StatusCode::UNAUTHORIZED, ServerErrorKind::Unauthorized),
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            ServerErrorKind::SchemaViolation,
+        ),
+        (StatusCode::NOT_FOUND, ServerErrorKind::NotFound),
+        (StatusCode::INTERNAL_SERVER_ERROR, ServerErrorKind::Internal),
+    ];
+    for (status, kind) in cases {
+        let body = json!({"error": "schema"}).to_string();
+        for error in [
+            ClientError::DBApiError(ServerError::new(Some(status), &body)),
+            ClientError::DBError(ServerError::new(Some(status), &body)),
+        ] {
+            assert_eq!(error.server_error().map(ServerError::kind), Some(kind));
+            assert_eq!(
+                error.is_precondition_failed(),
+                kind == ServerErrorKind::PreconditionFailed
+            );
+            assert_eq!(
+                error.is_unauthorized(),
+                kind == ServerErrorKind::Unauthorized
+            );
+            assert_eq!(
+                error.is_schema_violation(),
+                kind == ServerErrorKind::SchemaViolation
+            );
+            assert_eq!(error.is_not_found(), kind == ServerErrorKind::NotFound);
+        }
+    }
+    assert!(ClientError::APITokenInvalid.is_unauthorized());
+    assert!(ClientError::PingFailed.server_error().is_none());
+}
@@ -0,0 +1,286 @@
+use eventsourcingdb::{
+    EventCandidate, Subject,
+    client::{Client, RetryPolicy},
+    error::{ClientError, SubjectError},
+    request_options::{
+        ObserveEventMissingStrategy, ObserveEventsOptions, ObserveFromLatestEventOptions,
+        ReadEventMissingStrategy, ReadEventsOptions, ReadFromLatestEventOptions,
+    },
+    subject::{SubjectNode, SubjectTree},
+};
+use serde_json::json;
+
+#[test]
+fn validate_subjects() {
+    for subject in [
+        "/",
+        "/books",
+        "/books/42",
+        "/books/42/loans",
+        "/books/",
+        "/users/jane_doe-1",
+        "/users/JaneDoe1",
+    ] {
+        assert!(
+            Subject::new(subject).is_ok(),
+            "Expected {subject:?} to be valid"
+        );
+    }
+    for subject in [
+        "",
+        "books",
+        "//books",
+        "/books//42",
+        "/books//",
+        "/books/4 2",
+        "/books/\n",
+        "/users/jane.doe",
+        "/users/jäne",
+        "/books/{id}",
+    ] {
+        assert!(
+            Subject::new(subject).is_err(),
+            "Expected {subject:?} to be invalid"
+        );
+    }
+}
+
+#[test]
+fn navigate_subject_hierarchy() {
+    let root = Subject::root();
+    let books = root.join("books").unwrap();
+    let loan = books.join("42/loans/1").unwrap();
+
+    assert_eq!(books.as_str(), "/books");
+    assert_eq!(loan.as_str(), "/books/42/loans/1");
+    assert_eq!(
+        loan.components().collect::<Vec<_>>(),
+        ["books", "42", "loans", "1"]
+    );
+    assert_eq!(root.components().count(), 0);
+
+    assert_eq!(
+        loan.parent(),
+        Some(Subject::new("/books/42/loans").unwrap())
+    );
+    assert_eq!(books.parent(), Some(root.clone()));
+    assert_eq!(root.parent(), None);
+
+    assert!(root.is_ancestor_of(&books));
+    assert!(books.is_ancestor_of(&loan));
+    assert!(!books.is_ancestor_of(&books));
+    assert!(!loan.is_ancestor_of(&books));
+    assert!(!books.is_ancestor_of(&Subject::new("/bookshelves").unwrap()));
+
+    assert!(books.join("").is_err());
+    assert!(books.join("42/").is_err());
+    assert!(books.join("4.2").is_err());
+
+    let trailing = Subject::new("/books/42/").unwrap();
+    assert_eq!(trailing.components().collect::<Vec<_>>(), ["books", "42"]);
+    assert_eq!(trailing.parent(), Some(books.clone()));
+    assert!(books.is_ancestor_of(&trailing));
+    assert_eq!(trailing.join("loans").unwrap().as_str(), "/books/42/loans");
+}
+
+#[test]
+fn compare_subjects_with_trailing_slash() {
+    let books = Subject::new("/books").unwrap();
+    let books_with_slash = Subject::new("/books/").unwrap();
+    let book = Subject::new("/books/42").unwrap();
+
+    // Both spellings are valid, but they are different subjects.
+    assert_ne!(books, books_with_slash);
+    assert_eq!(books.parent(), Some(Subject::root()));
+    assert_eq!(books_with_slash.parent(), Some(Subject::root()));
+    assert_eq!(book.parent(), Some(books.clone()));
+
+    // Neither is an ancestor of the other, but both are ancestors of the subjects below them.
+    assert!(!books.is_ancestor_of(&books_with_slash));
+    assert!(!books_with_slash.is_ancestor_of(&books));
+    assert!(books.is_ancestor_of(&book));
+    assert!(books_with_slash.is_ancestor_of(&book));
+    assert!(Subject::root().is_ancestor_of(&books_with_slash));
+}
+
+#[test]
+fn serialize_subject() {
+    let subject = Subject::new("/books/42").unwrap();
+    assert_eq!(serde_json::to_value(&subject).unwrap(), json!("/books/42"));
+    assert_eq!(
+        serde_json::from_value::<Subject>(json!("/books/42")).unwrap(),
+        subject
+    );
+    assert!(serde_json::from_value::<Subject>(json!("books/42")).is_err());
+}
+
+#[tokio::test]
+async fn client_rejects_malformed_subjects_before_requests() {
+    // Nothing listens on this port, so every request that is sent fails with a different error.
+    let client = Client::new("http://localhost:1/".parse().unwrap(), "secrettoken");
+    let is_invalid_subject = |error: &ClientError| matches!(error, ClientError::InvalidSubject(SubjectError { subject, .. }) if subject == "books/");
+
+    let result = client.read_events("books/", None).await;
+    assert!(result.as_ref().err().is_some_and(is_invalid_subject));
+
+    let result = client.observe_events("books/", None).await;
+    assert!(result.as_ref().err().is_some_and(is_invalid_subject));
+
+    let books = Subject::new("/books").unwrap();
+    let result = client.read_events(&books, None).await;
+    assert!(
+        result
+            .as_ref()
+            .err()
+            .is_some_and(|error| !is_invalid_subject(error))
+    );
+
+    let result = client.list_subjects(Some("books/")).await;
+    assert!(result.as_ref().err().is_some_and(is_invalid_subject));
+
+    let from_latest_event = ObserveFromLatestEventOptions {
+        if_event_is_missing: ObserveEventMissingStrategy::ObserveEverything,
+        subject: "books/",
+        ty: "io.eventsourcingdb.test",
+    };
+    let options = ObserveEventsOptions {
+        from_latest_event: Some(from_latest_event),
+        ..Default::default()
+    };
+    let result = client.observe_events("/books", Some(options.clone())).await;
+    assert!(result.as_ref().err().is_some_and(is_invalid_subject));
+    let result = client
+        .observe_events_resilient("/books", Some(options), RetryPolicy::default())
+        .await;
+    assert!(result.as_ref().err().is_some_and(is_invalid_subject));
+
+    let from_latest_event = ReadFromLatestEventOptions {
+        if_event_is_missing: ReadEventMissingStrategy::ReadEverything,
+        subject: "books/",
+        ty: "io.eventsourcingdb.test",
+    };
+    let options = ReadEventsOptions {
+        from_latest_event: Some(from_latest_event),
+        ..Default::default()
+    };
+    let result = client.read_events("/books", Some(options)).await;
+    assert!(result.as_ref().err().is_some_and(is_invalid_subject));
+
+    let candidate = EventCandidate::builder()
+        .source("https://www.eventsourcingdb.io".to_string())
+        .data(json!({"value": 1}))
+        .subject("books/".to_string())
+        .ty("io.eventsourcingdb.test".to_string())
+        .build();
+    let result = client.write_events(vec![candidate], vec![]).await;
+    assert!(result.as_ref().err().is_some_and(is_invalid_subject));
+
+    let subject = Subject::new("/books").unwrap();
+    let result = client.read_events(&subject, None).await;
+    assert!(
+        result
+            .as_ref()
+            .err()
+            .is_some_and(|error| !is_invalid_subject(error)),
+        "Expected a valid subject to be sent to the DB"
+    );
+}
+
+fn create_test_tree() -> SubjectTree {
+    let subjects = [
+        "/books/42",
+        "/books/42/loans",
+        "/books/43/loans/1",
+        "/users/jane",
+        "/authors/1",
+    ];
+    SubjectTree::new(
+        Subject::new("/books").unwrap(),
+        subjects
+            .into_iter()
+            .map(|subject| Subject::new(subject).unwrap()),
+    )
+}
+
+fn subjects_of<'a>(nodes: impl IntoIterator<Item = &'a SubjectNode>) -> Vec<String> {
+    nodes
+        .into_iter()
+        .map(|node| node.subject().to_string())
+        .collect()
+}
+
+#[test]
+fn build_subject_tree_below_base() {
+    let tree = create_test_tree();
+    let root = tree.root();
+    assert_eq!(root.subject().as_str(), "/books");
+    assert!(!root.has_events());
+    assert_eq!(root.child_count(), 2);
+    assert_eq!(root.descendant_count(), 5);
+
+    let book = tree.get(&Subject::new("/books/43").unwrap()).unwrap();
+    assert_eq!(book.name(), Some("43"));
+    assert!(!book.has_events(), "Intermediate subjects have no events");
+    assert_eq!(subjects_of(book.children()), ["/books/43/loans"]);
+    assert!(tree.get(&Subject::new("/users/jane").unwrap()).is_none());
+    assert!(tree.get(&Subject::new("/books/44").unwrap()).is_none());
+}
+
+#[test]
+fn list_subject_tree_with_max_depth() {
+    let tree = create_test_tree();
+    assert_eq!(
+        subjects_of(tree.descendants(Some(1))),
+        ["/books/42", "/books/43"]
+    );
+    assert_eq!(
+        subjects_of(tree.descendants(Some(2))),
+        [
+            "/books/42",
+            "/books/42/loans",
+            "/books/43",
+            "/books/43/loans"
+        ]
+    );
+    assert_eq!(tree.descendants(None).len(), 5);
+    assert!(tree.descendants(Some(0)).is_empty());
+}
+
+#[test]
+fn filter_subject_tree_with_glob_pattern() {
+    let tree = create_test_tree();
+    assert_eq!(
+        subjects_of(tree.matching("/books/*/loans").unwrap()),
+        ["/books/42/loans", "/books/43/loans"]
+    );
+    assert_eq!(
+        subjects_of(tree.matching("/books/4*3").unwrap()),
+        ["/books/43"]
+    );
+    assert_eq!(
+        subjects_of(tree.matching("/books/**/1").unwrap()),
+        ["/books/43/loans/1"]
+    );
+    assert_eq!(tree.matching("/books/**").unwrap().len(), 6);
+    assert!(tree.matching("/users/*").unwrap().is_empty());
+    assert!(tree.matching("books/*").is_err());
+}
+
+#[test]
+fn match_subject_tree_with_many_wildcards() {
+    let components: Vec<String> = (0..40).map(|index| index.to_string()).collect();
+    let subject = Subject::new(format!("/{}", components.join("/"))).unwrap();
+    let tree = SubjectTree::new(Subject::root(), [subject]);
+
+    let pattern = format!("/{}/x", ["**"; 30].join("/"));
+    assert!(tree.matching(&pattern).unwrap().is_empty());
+    let pattern = format!("/{}/39", ["**"; 30].join("/"));
+    assert_eq!(
+        subjects_of(tree.matching(&pattern).unwrap()),
+        [format!("/{}", components.join("/"))]
+    );
+    assert_eq!(
+        subjects_of(tree.matching("/**/1*/**/3*/**/39").unwrap()),
+        [format!("/{}", components.join("/"))]
+    );
+}
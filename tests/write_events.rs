@@ -1,6 +1,12 @@
 mod utils;
 
-use eventsourcingdb::{EventCandidate, Precondition, TraceInfo};
+use eventsourcingdb::{
+    EventCandidate, Precondition, Preconditions, TraceInfo,
+    client::Client,
+    error::{ClientError, PreconditionError},
+    eventql::{EventQl, Expr},
+};
+use futures::TryStreamExt;
 use serde_json::json;
 use utils::{
     assert_event_match_eventcandidate, assert_events_match_eventcandidates,
@@ -78,6 +84,33 @@ async fn write_event_with_is_pristine_condition_on_non_empty_subject() {
     assert!(result.is_err(), "Expected an error, but got: {result:?}");
 }
 
+#[tokio::test]
+async fn write_event_with_failed_precondition_reports_reason() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+
+    let event_candidate = create_test_eventcandidate("/test", json!({"value": 1}));
+    client
+        .write_events(vec![event_candidate.clone()], vec![])
+        .await
+        .expect("Failed to write initial event");
+    let error = client
+        .write_events(
+            vec![event_candidate.clone()],
+            vec![Precondition::IsSubjectPristine {
+                subject: event_candidate.subject.clone(),
+            }],
+        )
+        .await
+        .expect_err("Expected an error");
+    assert!(
+        error.is_precondition_failed(),
+        "Expected a failed precondition, but got: {error:?}"
+    );
+    let server_error = error.server_error().expect("Expected a server error");
+    assert!(!server_error.message().is_empty());
+}
+
 #[tokio::test]
 async fn write_event_with_is_subject_populated_condition_on_empty_subject() {
     let container = create_test_container().await;
@@ -290,6 +323,43 @@ async fn write_events_with_is_eventql_true_condition() {
     assert!(result.is_ok(), "Expected an ok, but got: {result:?}");
 }
 
+#[tokio::test]
+async fn write_events_with_built_eventql_query_condition() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+
+    let event_candidate = create_test_eventcandidate("/test/42", json!({"value": 1}));
+    let _ = client
+        .write_events(vec![event_candidate.clone()], vec![])
+        .await
+        .expect("Failed to write initial event");
+
+    let is_pristine = EventQl::from("e")
+        .unwrap()
+        .in_events()
+        .where_(Expr::field("e.subject").unwrap().equals("/test/42"))
+        .project_into(Expr::function("COUNT", []).unwrap().equals(0));
+    let result = client
+        .write_events(vec![event_candidate.clone()], vec![is_pristine.into()])
+        .await;
+    assert!(
+        result
+            .as_ref()
+            .is_err_and(ClientError::is_precondition_failed),
+        "Expected a failed precondition, but got: {result:?}"
+    );
+
+    let is_other_pristine = EventQl::from("e")
+        .unwrap()
+        .in_events()
+        .where_(Expr::field("e.subject").unwrap().equals("/test/43"))
+        .project_into(Expr::function("COUNT", []).unwrap().equals(0));
+    let result = client
+        .write_events(vec![event_candidate], vec![is_other_pristine.into()])
+        .await;
+    assert!(result.is_ok(), "Expected an ok, but got: {result:?}");
+}
+
 #[tokio::test]
 async fn write_events_with_is_subject_on_event_id_condition_on_non_empty_subject_correct_id() {
     let container = create_test_container().await;
@@ -392,3 +462,205 @@ async fn write_single_event_with_traceparent_and_state() {
 
     assert_event_match_eventcandidate(&response_event, &event, None, None);
 }
+
+#[tokio::test]
+async fn write_events_with_schema_validation() {
+    let container = create_test_container().await;
+    let client = Client::builder()
+        .base_url(container.get_base_url().await.unwrap())
+        .api_token(container.get_api_token())
+        .validate_event_schemas()
+        .build();
+    client
+        .register_event_schema(
+            "io.eventsourcingdb.test",
+            &json!({
+                "type": "object",
+                "properties": {
+                    "value": { "type": "integer" }
+                },
+                "required": ["value"]
+            }),
+        )
+        .await
+        .expect("Failed to register event schema");
+
+    let valid = create_test_eventcandidate("/test", json!({"value": 1}));
+    let invalid = create_test_eventcandidate("/test", json!({"value": "two"}));
+    client
+        .write_events(vec![valid.clone()], vec![])
+        .await
+        .expect("Failed to write valid event");
+
+    let result = client.write_events(vec![valid, invalid], vec![]).await;
+    match result {
+        Err(ref error @ ClientError::EventSchemaViolation(ref violation)) => {
+            assert!(error.is_schema_violation());
+            assert_eq!(violation.index, 1);
+            assert_eq!(violation.event_type, "io.eventsourcingdb.test");
+            assert_eq!(violation.pointer, "/value");
+        }
+        other => panic!("Expected a schema violation, but got: {other:?}"),
+    }
+
+    let events: Vec<_> = client
+        .read_events("/test", None)
+        .await
+        .expect("Failed to read events")
+        .try_collect()
+        .await
+        .expect("Failed to read events");
+    assert_eq!(
+        events.len(),
+        1,
+        "Expected the invalid batch not to be written"
+    );
+}
+
+#[tokio::test]
+async fn write_events_with_schema_validation_reads_schema_from_db() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+    client
+        .register_event_schema(
+            "io.eventsourcingdb.test",
+            &json!({
+                "type": "object",
+                "properties": {
+                    "value": { "type": "integer" }
+                },
+                "required": ["value"]
+            }),
+        )
+        .await
+        .expect("Failed to register event schema");
+
+    let validating_client = Client::builder()
+        .base_url(container.get_base_url().await.unwrap())
+        .api_token(container.get_api_token())
+        .validate_event_schemas()
+        .build();
+    let invalid = create_test_eventcandidate("/test", json!({"other": 1}));
+    let result = validating_client.write_events(vec![invalid], vec![]).await;
+    match result {
+        Err(ClientError::EventSchemaViolation(violation)) => {
+            assert_eq!(violation.index, 0);
+            assert_eq!(violation.pointer, "");
+        }
+        other => panic!("Expected a schema violation, but got: {other:?}"),
+    }
+
+    let unknown_type = EventCandidate::builder()
+        .source("https://www.eventsourcingdb.io".to_string())
+        .data(json!({"other": 1}))
+        .subject("/test".to_string())
+        .ty("io.eventsourcingdb.test.unknown".to_string())
+        .build();
+    validating_client
+        .write_events(vec![unknown_type], vec![])
+        .await
+        .expect("Failed to write event without schema");
+}
+
+#[tokio::test]
+async fn write_events_with_preconditions_builder() {
+    let container = create_test_container().await;
+    let client = container.get_client().await.unwrap();
+
+    let _ = client
+        .write_events(
+            vec![create_test_eventcandidate("/books/42", json!({"value": 1}))],
+            vec![],
+        )
+        .await
+        .expect("Failed to write initial event");
+    let loaded: Vec<_> = client
+        .read_events("/books/42", None)
+        .await
+        .expect("Failed to read events")
+        .try_collect()
+        .await
+        .expect("Failed to read events");
+
+    let event_candidates = vec![
+        create_test_eventcandidate("/books/42", json!({"value": 2})),
+        create_test_eventcandidate("/loans/1", json!({"value": 1})),
+    ];
+    let preconditions = Preconditions::new()
+        .with_subjects_on_events(&loaded)
+        .with_pristine_new_subjects(&event_candidates);
+    let result = client
+        .write_events(
+            event_candidates.clone(),
+            preconditions
+                .clone()
+                .build_for(&event_candidates)
+                .expect("Invalid preconditions"),
+        )
+        .await;
+    assert!(result.is_ok(), "Expected an ok, but got: {result:?}");
+
+    let result = client
+        .write_events(event_candidates, preconditions.build())
+        .await;
+    assert!(
+        result
+            .as_ref()
+            .is_err_and(ClientError::is_precondition_failed),
+        "Expected a failed precondition, but got: {result:?}"
+    );
+}
+
+#[test]
+fn preconditions_builder_adds_pristine_only_for_new_subjects() {
+    let event_candidates = vec![
+        create_test_eventcandidate("/books/42", json!({"value": 1})),
+        create_test_eventcandidate("/loans/1", json!({"value": 1})),
+        create_test_eventcandidate("/loans/1", json!({"value": 2})),
+    ];
+    let preconditions = Preconditions::new()
+        .with_subject_populated("/books/42")
+        .with_pristine_new_subjects(&event_candidates)
+        .build();
+    assert_eq!(
+        preconditions,
+        vec![
+            Precondition::IsSubjectPopulated {
+                subject: "/books/42".to_string(),
+            },
+            Precondition::IsSubjectPristine {
+                subject: "/loans/1".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn preconditions_builder_rejects_unknown_subjects() {
+    let event_candidates = vec![create_test_eventcandidate("/books/42", json!({"value": 1}))];
+    let result = Preconditions::new()
+        .with_subject_pristine("/books/43")
+        .with_eventql_query("FROM e IN events PROJECT INTO COUNT() == 0")
+        .build_for(&event_candidates);
+    assert_eq!(
+        result,
+        Err(PreconditionError::UnknownSubject {
+            subject: "/books/43".to_string(),
+        })
+    );
+}
+
+#[test]
+fn preconditions_builder_rejects_conflicting_preconditions() {
+    let event_candidates = vec![create_test_eventcandidate("/books/42", json!({"value": 1}))];
+    let result = Preconditions::new()
+        .with_subject_populated("/books/42")
+        .with_subject_pristine("/books/42")
+        .build_for(&event_candidates);
+    assert_eq!(
+        result,
+        Err(PreconditionError::Conflicting {
+            subject: "/books/42".to_string(),
+        })
+    );
+}